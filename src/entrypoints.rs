@@ -1,9 +1,8 @@
 use cosmwasm_std::{entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response};
-use valkyrie_qualifier::query_msgs::QueryMsg;
 
 use crate::errors::ContractError;
 use crate::executions::ExecuteResult;
-use crate::msgs::{ExecuteMsg, InstantiateMsg};
+use crate::msgs::{ExecuteMsg, InstantiateMsg, QueryMsg};
 use crate::{executions, queries};
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -21,14 +20,58 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> ExecuteResult {
     match msg {
-        ExecuteMsg::Configure {
-            admin,
-            pool,
-            gov,
+        ExecuteMsg::Configure(msg) => executions::configure(deps, env, info, msg),
+        ExecuteMsg::UpdateRequirement { requirement } => {
+            executions::update_requirement(deps, env, info, requirement)
+        }
+        ExecuteMsg::SetCampaignProfile {
+            campaign,
+            requirement,
+            continue_option_on_fail,
+            continue_option_on_referrer_fail,
+        } => executions::set_campaign_profile(
+            deps,
+            env,
+            info,
+            campaign,
+            requirement,
             continue_option_on_fail,
-        } => executions::configure(deps, env, info, admin, pool, gov, continue_option_on_fail),
+            continue_option_on_referrer_fail,
+        ),
+        ExecuteMsg::RemoveCampaignProfile { campaign } => {
+            executions::remove_campaign_profile(deps, env, info, campaign)
+        }
+        ExecuteMsg::AddCampaign { campaign } => executions::add_campaign(deps, env, info, campaign),
+        ExecuteMsg::RemoveCampaign { campaign } => {
+            executions::remove_campaign(deps, env, info, campaign)
+        }
+        ExecuteMsg::SetQuota { campaign, quota } => {
+            executions::set_quota(deps, env, info, campaign, quota)
+        }
+        ExecuteMsg::AddMerkleRoot { campaign, root } => {
+            executions::add_merkle_root(deps, env, info, campaign, root)
+        }
+        ExecuteMsg::RemoveMerkleRoot { campaign, root } => {
+            executions::remove_merkle_root(deps, env, info, campaign, root)
+        }
+        ExecuteMsg::RegisterMerkleProof {
+            campaign,
+            root,
+            proof,
+        } => executions::register_merkle_proof(deps, env, info, campaign, root, proof),
+        ExecuteMsg::AddToAccessList { list, addresses } => {
+            executions::add_to_access_list(deps, env, info, list, addresses)
+        }
+        ExecuteMsg::RemoveFromAccessList { list, addresses } => {
+            executions::remove_from_access_list(deps, env, info, list, addresses)
+        }
         ExecuteMsg::Prepare {} => executions::prepare(deps, env, info),
         ExecuteMsg::Qualify(msg) => executions::qualify(deps, env, info, msg),
+        ExecuteMsg::PruneSnapshots {
+            before_height,
+            start_after,
+            limit,
+        } => executions::prune_snapshots(deps, env, info, before_height, start_after, limit),
     }
 }
 
@@ -39,6 +82,80 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractErro
             to_binary(&queries::qualify_without_checking_deposit(deps, env, msg)?)
         }
         QueryMsg::Requirement {} => to_binary(&queries::requirement(deps, env)?),
+        QueryMsg::Evaluate(msg) => to_binary(&queries::evaluate(deps, env, msg)?),
+        QueryMsg::Config {} => to_binary(&queries::config(deps, env)?),
+        QueryMsg::PrepareStatus { address, height } => {
+            to_binary(&queries::prepare_status(deps, env, address, height)?)
+        }
+        QueryMsg::State {} => to_binary(&queries::state(deps, env)?),
+        QueryMsg::Campaigns { start_after, limit } => {
+            to_binary(&queries::campaigns(deps, env, start_after, limit)?)
+        }
+        QueryMsg::CampaignProfile { campaign } => {
+            to_binary(&queries::campaign_profile(deps, env, campaign)?)
+        }
+        QueryMsg::CampaignProfiles { start_after, limit } => {
+            to_binary(&queries::campaign_profiles(deps, env, start_after, limit)?)
+        }
+        QueryMsg::AccessList {
+            list,
+            start_after,
+            limit,
+        } => to_binary(&queries::access_list(deps, env, list, start_after, limit)?),
+        QueryMsg::RemainingSlots { campaign } => {
+            to_binary(&queries::remaining_slots(deps, env, campaign)?)
+        }
+        QueryMsg::MerkleRoots {
+            campaign,
+            start_after,
+            limit,
+        } => to_binary(&queries::merkle_roots(
+            deps,
+            env,
+            campaign,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::MerkleRegistration { campaign, address } => {
+            to_binary(&queries::merkle_registration(deps, env, campaign, address)?)
+        }
+        QueryMsg::Stats {} => to_binary(&queries::stats(deps, env)?),
+        QueryMsg::CampaignStats { start_after, limit } => {
+            to_binary(&queries::campaign_stats(deps, env, start_after, limit)?)
+        }
+        QueryMsg::QualificationsByActor {
+            actor,
+            start_after,
+            limit,
+        } => to_binary(&queries::qualifications_by_actor(
+            deps,
+            env,
+            actor,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::QualificationsByCampaign {
+            campaign,
+            start_after,
+            limit,
+        } => to_binary(&queries::qualifications_by_campaign(
+            deps,
+            env,
+            campaign,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::QualificationsByOutcome {
+            outcome,
+            start_after,
+            limit,
+        } => to_binary(&queries::qualifications_by_outcome(
+            deps,
+            env,
+            outcome,
+            start_after,
+            limit,
+        )?),
     }?;
 
     Ok(result)
@@ -15,4 +15,10 @@ pub enum ContractError {
 
     #[error("Invalid zero amount")]
     InvalidZeroAmount {},
+
+    #[error("Invalid argument: {msg}")]
+    InvalidArgument { msg: String },
+
+    #[error("Invalid merkle proof")]
+    InvalidMerkleProof {},
 }
@@ -1,10 +1,21 @@
-use cosmwasm_std::{to_binary, DepsMut, Env, MessageInfo, Response};
+use cosmwasm_bignumber::{Decimal256, Uint256};
+use cosmwasm_std::{to_binary, Api, DepsMut, Env, MessageInfo, Response};
+use cw0::{Duration, Expiration};
+use cw20::Denom;
 use valkyrie_qualifier::{QualificationMsg, QualifiedContinueOption};
 
 use crate::errors::ContractError;
-use crate::msgs::InstantiateMsg;
+use crate::merkle;
+use crate::msgs::{to_json_string, ConfigureMsg, InstantiateMsg, PoolInfo};
 use crate::queries;
-use crate::states::{save_prepare_status, QualifierConfig, Querier, Requirement};
+use crate::states::{
+    denom_to_string, deregister_campaign, deregister_merkle_root, has_campaign_quotas,
+    has_merkle_root, increase_qualified_count, is_registered_campaign, load_valid_prepare_status,
+    next_qualification_record_id, prune_prepare_statuses, register_campaign, register_merkle_root,
+    remove_prepare_status, save_campaign_quota, save_merkle_registration, save_prepare_status,
+    save_qualification_record, update_stats, validate_denom, AccessList, CampaignProfile,
+    PoolConfig, PrepareStatus, QualificationRecord, QualifierConfig, Querier, Requirement,
+};
 
 pub type ExecuteResult = Result<Response, ContractError>;
 
@@ -16,32 +27,108 @@ pub fn instantiate(
 ) -> ExecuteResult {
     let response = Response::new().add_attribute("action", "instantiate");
 
-    QualifierConfig {
+    let config = QualifierConfig {
         admin: info.sender,
-        pool: deps.api.addr_validate(msg.pool.as_str())?,
+        pools: validate_pools(deps.api, msg.pools)?,
         gov: deps.api.addr_validate(msg.gov.as_str())?,
         continue_option_on_fail: msg.continue_option_on_fail,
+        continue_option_on_referrer_fail: msg.continue_option_on_referrer_fail,
+        prepare_validity: msg.prepare_validity.unwrap_or(Duration::Height(0)),
+        campaign_restricted: msg.campaign_restricted.unwrap_or(false),
+        snapshot_denoms: validate_denoms(deps.api, msg.snapshot_denoms.unwrap_or_default())?,
+        holder_policy: msg.holder_policy.unwrap_or_default(),
+        allowlist_policy: msg.allowlist_policy.unwrap_or_default(),
+        participant_cap: msg.participant_cap,
+        start_time: msg.start_time.and_then(window_bound),
+        end_time: msg.end_time.and_then(window_bound),
+        continue_option_on_window_fail: msg.continue_option_on_window_fail,
+    };
+    validate_window(&config)?;
+    validate_quota_restriction(&config, false)?;
+    config.save(deps.storage)?;
+
+    msg.requirement
+        .validate(deps.api, &config.snapshot_denoms)?;
+    msg.requirement.save(deps.storage)?;
+
+    Ok(response)
+}
+
+fn window_bound(bound: Expiration) -> Option<Expiration> {
+    match bound {
+        Expiration::Never {} => None,
+        bound => Some(bound),
     }
-    .save(deps.storage)?;
+}
 
-    Requirement {
-        deposit_delta: msg.deposit_delta,
-        min_mine_stake_amount: msg.min_mine_stake_amount,
+// qualified counts are only trustworthy when the callers are restricted to registered campaigns
+fn validate_quota_restriction(
+    config: &QualifierConfig,
+    has_campaign_quotas: bool,
+) -> Result<(), ContractError> {
+    if !config.campaign_restricted && (config.participant_cap.is_some() || has_campaign_quotas) {
+        return Err(ContractError::InvalidArgument {
+            msg: "Quota requires campaign_restricted".to_string(),
+        });
     }
-    .save(deps.storage)?;
 
-    Ok(response)
+    Ok(())
 }
 
-pub fn configure(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    admin: Option<String>,
-    pool: Option<String>,
-    gov: Option<String>,
-    continue_option_on_fail: Option<QualifiedContinueOption>,
-) -> ExecuteResult {
+fn validate_window(config: &QualifierConfig) -> Result<(), ContractError> {
+    if let (Some(start_time), Some(end_time)) = (&config.start_time, &config.end_time) {
+        // mismatched kinds are not comparable and left as is
+        if start_time >= end_time {
+            return Err(ContractError::InvalidArgument {
+                msg: "Start time must be before end time".to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_pools(api: &dyn Api, pools: Vec<PoolInfo>) -> Result<Vec<PoolConfig>, ContractError> {
+    if pools.is_empty() {
+        return Err(ContractError::InvalidArgument {
+            msg: "At least one pool is required".to_string(),
+        });
+    }
+
+    let mut result: Vec<PoolConfig> = vec![];
+    for pool in pools.into_iter() {
+        let address = api.addr_validate(pool.address.as_str())?;
+        if result.iter().any(|p| p.address == address) {
+            return Err(ContractError::InvalidArgument {
+                msg: format!("Duplicated pool: {}", address),
+            });
+        }
+
+        let weight = pool.weight.unwrap_or_else(Decimal256::one);
+        if weight.is_zero() {
+            return Err(ContractError::InvalidZeroAmount {});
+        }
+
+        result.push(PoolConfig { address, weight });
+    }
+
+    Ok(result)
+}
+
+fn validate_denoms(api: &dyn Api, denoms: Vec<Denom>) -> Result<Vec<Denom>, ContractError> {
+    for (i, denom) in denoms.iter().enumerate() {
+        validate_denom(api, denom)?;
+        if denoms[..i].contains(denom) {
+            return Err(ContractError::InvalidArgument {
+                msg: format!("Duplicated denom: {}", denom_to_string(denom)),
+            });
+        }
+    }
+
+    Ok(denoms)
+}
+
+pub fn configure(deps: DepsMut, _env: Env, info: MessageInfo, msg: ConfigureMsg) -> ExecuteResult {
     let response = Response::new().add_attribute("action", "configure");
 
     let mut config = QualifierConfig::load(deps.storage)?;
@@ -49,53 +136,478 @@ pub fn configure(
         return Err(ContractError::Unauthorized {});
     }
 
-    if let Some(v) = admin {
+    if let Some(v) = msg.admin {
         config.admin = deps.api.addr_validate(v.as_str())?;
     }
-    if let Some(v) = pool {
-        config.pool = deps.api.addr_validate(v.as_str())?;
+    if let Some(v) = msg.pools {
+        config.pools = validate_pools(deps.api, v)?;
     }
-    if let Some(v) = gov {
+    if let Some(v) = msg.gov {
         config.gov = deps.api.addr_validate(v.as_str())?;
     }
-    if let Some(v) = continue_option_on_fail {
+    if let Some(v) = msg.continue_option_on_fail {
         config.continue_option_on_fail = v;
     }
+    if let Some(v) = msg.continue_option_on_referrer_fail {
+        config.continue_option_on_referrer_fail = v.to_option();
+    }
+    if let Some(v) = msg.prepare_validity {
+        config.prepare_validity = v;
+    }
+    if let Some(v) = msg.campaign_restricted {
+        config.campaign_restricted = v;
+    }
+    if let Some(v) = msg.snapshot_denoms {
+        config.snapshot_denoms = validate_denoms(deps.api, v)?;
+
+        // requirements in use must not lose their snapshots
+        Requirement::load(deps.storage)?.validate(deps.api, &config.snapshot_denoms)?;
+        for profile in CampaignProfile::load_all(deps.storage)? {
+            profile
+                .requirement
+                .validate(deps.api, &config.snapshot_denoms)?;
+        }
+    }
+    if let Some(v) = msg.holder_policy {
+        config.holder_policy = v;
+    }
+    if let Some(v) = msg.allowlist_policy {
+        config.allowlist_policy = v;
+    }
+    if let Some(v) = msg.start_time {
+        config.start_time = window_bound(v);
+    }
+    if let Some(v) = msg.end_time {
+        config.end_time = window_bound(v);
+    }
+    if let Some(v) = msg.continue_option_on_window_fail {
+        config.continue_option_on_window_fail = v.to_option();
+    }
 
+    validate_window(&config)?;
+    validate_quota_restriction(&config, has_campaign_quotas(deps.storage))?;
     config.save(deps.storage)?;
 
     Ok(response)
 }
 
+pub fn update_requirement(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    requirement: Requirement,
+) -> ExecuteResult {
+    let response = Response::new().add_attribute("action", "update_requirement");
+
+    let config = QualifierConfig::load(deps.storage)?;
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    requirement.validate(deps.api, &config.snapshot_denoms)?;
+
+    let prev_requirement = Requirement::load(deps.storage)?;
+    requirement.save(deps.storage)?;
+
+    Ok(response
+        .add_attribute("prev_requirement", to_json_string(&prev_requirement)?)
+        .add_attribute("requirement", to_json_string(&requirement)?))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn set_campaign_profile(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    campaign: String,
+    requirement: Requirement,
+    continue_option_on_fail: QualifiedContinueOption,
+    continue_option_on_referrer_fail: Option<QualifiedContinueOption>,
+) -> ExecuteResult {
+    let response = Response::new().add_attribute("action", "set_campaign_profile");
+
+    let config = QualifierConfig::load(deps.storage)?;
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    requirement.validate(deps.api, &config.snapshot_denoms)?;
+
+    let campaign = deps.api.addr_validate(campaign.as_str())?;
+    let profile = CampaignProfile {
+        requirement,
+        continue_option_on_fail,
+        continue_option_on_referrer_fail,
+    };
+    profile.save(deps.storage, &campaign)?;
+
+    Ok(response
+        .add_attribute("campaign", campaign)
+        .add_attribute("requirement", to_json_string(&profile.requirement)?)
+        .add_attribute(
+            "continue_option_on_fail",
+            profile.continue_option_on_fail.to_string(),
+        ))
+}
+
+pub fn remove_campaign_profile(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    campaign: String,
+) -> ExecuteResult {
+    let response = Response::new().add_attribute("action", "remove_campaign_profile");
+
+    let config = QualifierConfig::load(deps.storage)?;
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let campaign = deps.api.addr_validate(campaign.as_str())?;
+    CampaignProfile::remove(deps.storage, &campaign);
+
+    Ok(response.add_attribute("campaign", campaign))
+}
+
+pub fn add_campaign(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    campaign: String,
+) -> ExecuteResult {
+    let response = Response::new().add_attribute("action", "add_campaign");
+
+    let config = QualifierConfig::load(deps.storage)?;
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let campaign = deps.api.addr_validate(campaign.as_str())?;
+    register_campaign(deps.storage, &campaign)?;
+
+    Ok(response.add_attribute("campaign", campaign))
+}
+
+pub fn remove_campaign(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    campaign: String,
+) -> ExecuteResult {
+    let response = Response::new().add_attribute("action", "remove_campaign");
+
+    let config = QualifierConfig::load(deps.storage)?;
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let campaign = deps.api.addr_validate(campaign.as_str())?;
+    deregister_campaign(deps.storage, &campaign);
+
+    Ok(response.add_attribute("campaign", campaign))
+}
+
+pub fn set_quota(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    campaign: Option<String>,
+    quota: Option<u64>,
+) -> ExecuteResult {
+    let mut response = Response::new().add_attribute("action", "set_quota");
+
+    let mut config = QualifierConfig::load(deps.storage)?;
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match campaign {
+        Some(campaign) => {
+            let campaign = deps.api.addr_validate(campaign.as_str())?;
+            save_campaign_quota(deps.storage, &campaign, quota)?;
+            response = response.add_attribute("campaign", campaign);
+        }
+        None => config.participant_cap = quota,
+    }
+    validate_quota_restriction(&config, has_campaign_quotas(deps.storage))?;
+    config.save(deps.storage)?;
+
+    Ok(response.add_attribute(
+        "quota",
+        quota
+            .map(|q| q.to_string())
+            .unwrap_or_else(|| "none".to_string()),
+    ))
+}
+
+fn validate_merkle_root(root: &str) -> Result<merkle::Hash, ContractError> {
+    merkle::from_hex(root).ok_or_else(|| ContractError::InvalidArgument {
+        msg: format!("Invalid merkle root: {}", root),
+    })
+}
+
+pub fn add_merkle_root(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    campaign: String,
+    root: String,
+) -> ExecuteResult {
+    let response = Response::new().add_attribute("action", "add_merkle_root");
+
+    let config = QualifierConfig::load(deps.storage)?;
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let campaign = deps.api.addr_validate(campaign.as_str())?;
+    let root = merkle::to_hex(&validate_merkle_root(root.as_str())?);
+    register_merkle_root(deps.storage, &campaign, root.as_str())?;
+
+    Ok(response
+        .add_attribute("campaign", campaign)
+        .add_attribute("root", root))
+}
+
+pub fn remove_merkle_root(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    campaign: String,
+    root: String,
+) -> ExecuteResult {
+    let response = Response::new().add_attribute("action", "remove_merkle_root");
+
+    let config = QualifierConfig::load(deps.storage)?;
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let campaign = deps.api.addr_validate(campaign.as_str())?;
+    let root = merkle::to_hex(&validate_merkle_root(root.as_str())?);
+    deregister_merkle_root(deps.storage, &campaign, root.as_str());
+
+    Ok(response
+        .add_attribute("campaign", campaign)
+        .add_attribute("root", root))
+}
+
+pub fn register_merkle_proof(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    campaign: String,
+    root: String,
+    proof: Vec<String>,
+) -> ExecuteResult {
+    let response = Response::new().add_attribute("action", "register_merkle_proof");
+
+    let campaign = deps.api.addr_validate(campaign.as_str())?;
+    let root_hash = validate_merkle_root(root.as_str())?;
+    let root = merkle::to_hex(&root_hash);
+    if !has_merkle_root(deps.storage, &campaign, root.as_str()) {
+        return Err(ContractError::InvalidArgument {
+            msg: format!("Unknown merkle root: {}", root),
+        });
+    }
+
+    let proof = proof
+        .iter()
+        .map(|p| merkle::from_hex(p.as_str()))
+        .collect::<Option<Vec<_>>>()
+        .ok_or(ContractError::InvalidMerkleProof {})?;
+    if !merkle::verify(&root_hash, info.sender.as_str(), &proof) {
+        return Err(ContractError::InvalidMerkleProof {});
+    }
+
+    save_merkle_registration(deps.storage, &campaign, &info.sender, root.as_str())?;
+
+    Ok(response
+        .add_attribute("campaign", campaign)
+        .add_attribute("address", info.sender)
+        .add_attribute("root", root))
+}
+
+pub fn add_to_access_list(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    list: AccessList,
+    addresses: Vec<String>,
+) -> ExecuteResult {
+    let response = Response::new().add_attribute("action", "add_to_access_list");
+
+    let config = QualifierConfig::load(deps.storage)?;
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for address in addresses.iter() {
+        let address = deps.api.addr_validate(address.as_str())?;
+        list.add(deps.storage, &address)?;
+    }
+
+    Ok(response
+        .add_attribute("list", list.name())
+        .add_attribute("count", addresses.len().to_string()))
+}
+
+pub fn remove_from_access_list(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    list: AccessList,
+    addresses: Vec<String>,
+) -> ExecuteResult {
+    let response = Response::new().add_attribute("action", "remove_from_access_list");
+
+    let config = QualifierConfig::load(deps.storage)?;
+    if !config.is_admin(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for address in addresses.iter() {
+        let address = deps.api.addr_validate(address.as_str())?;
+        list.remove(deps.storage, &address);
+    }
+
+    Ok(response
+        .add_attribute("list", list.name())
+        .add_attribute("count", addresses.len().to_string()))
+}
+
 pub fn prepare(deps: DepsMut, env: Env, info: MessageInfo) -> ExecuteResult {
     let response = Response::new().add_attribute("action", "prepare");
 
     let config = QualifierConfig::load(deps.storage)?;
     let querier = Querier::new(&deps.querier);
 
-    let pool_deposit = querier.load_pool_deposit(&config.pool, &info.sender)?;
+    let pool_deposit = querier.load_pool_deposits(&config.pools, &info.sender)?;
+    let balances = querier.load_balances(&config.snapshot_denoms, &info.sender)?;
 
-    save_prepare_status(deps.storage, &env.block.height, &info.sender, &pool_deposit)?;
+    save_prepare_status(
+        deps.storage,
+        &info.sender,
+        &PrepareStatus {
+            height: env.block.height,
+            pool_deposit,
+            balances,
+            // plus one to make the snapshot available within the prepared block
+            expiration: config.prepare_validity.plus_one().after(&env.block),
+        },
+    )?;
 
     Ok(response)
 }
 
-pub fn qualify(
-    deps: DepsMut,
-    env: Env,
-    _info: MessageInfo,
-    msg: QualificationMsg,
-) -> ExecuteResult {
+pub fn qualify(deps: DepsMut, env: Env, info: MessageInfo, msg: QualificationMsg) -> ExecuteResult {
     let mut response = Response::new().add_attribute("action", "qualify");
 
-    let result = queries::qualify(deps.as_ref(), env, msg)?;
+    let config = QualifierConfig::load(deps.storage)?;
+    if config.campaign_restricted
+        && (info.sender.as_str() != msg.campaign
+            || !is_registered_campaign(deps.storage, &info.sender))
+    {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let campaign = deps.api.addr_validate(msg.campaign.as_str())?;
+    let sender = deps.api.addr_validate(msg.sender.as_str())?;
+    let actor = deps.api.addr_validate(msg.actor.as_str())?;
+    let referrer = msg
+        .referrer
+        .as_ref()
+        .map(|r| deps.api.addr_validate(r.as_str()))
+        .transpose()?;
+    // only registered campaigns consume snapshots, take slots and leave history and stats.
+    // otherwise anyone could wipe out the snapshots of others by naming itself as the campaign.
+    let from_registered_campaign =
+        info.sender == campaign && is_registered_campaign(deps.storage, &campaign);
+    let evaluation = queries::qualify(deps.as_ref(), env.clone(), msg)?;
+    let result = evaluation.to_qualification_result()?;
+
+    if from_registered_campaign && result.continue_option == QualifiedContinueOption::Eligible {
+        increase_qualified_count(deps.storage, &campaign)?;
+    }
+
+    if from_registered_campaign {
+        let record = QualificationRecord {
+            id: next_qualification_record_id(deps.storage)?,
+            campaign: campaign.clone(),
+            sender: sender.clone(),
+            actor: actor.clone(),
+            referrer,
+            height: env.block.height,
+            time: env.block.time,
+            continue_option: result.continue_option.clone(),
+            reason: evaluation.failure_reason.clone(),
+        };
+        save_qualification_record(deps.storage, &record)?;
+    }
+
+    // snapshots are consumed by this qualification
+    let querier = Querier::new(&deps.querier);
+    let mut deposit_delta = Uint256::zero();
+    let holders = if from_registered_campaign {
+        config.holder_policy.holders(&sender, &actor)
+    } else {
+        vec![]
+    };
+    for holder in holders {
+        if let Some(prepare_status) = load_valid_prepare_status(deps.storage, &env.block, holder)? {
+            let pool_deposit = querier.load_pool_deposits(&config.pools, holder)?;
+            if pool_deposit > prepare_status.pool_deposit {
+                deposit_delta += pool_deposit - prepare_status.pool_deposit;
+            }
+            remove_prepare_status(deps.storage, &prepare_status.height, holder)?;
+        }
+    }
+
+    if from_registered_campaign {
+        update_stats(
+            deps.storage,
+            &campaign,
+            &result.continue_option,
+            evaluation.failure_reason.as_ref(),
+            deposit_delta,
+        )?;
+    }
 
     response = response
         .add_attribute(
             "qualified_continue_option",
             result.continue_option.to_string(),
         )
+        .add_attributes(
+            evaluation
+                .failure_reason
+                .map(|r| r.attributes())
+                .unwrap_or_default(),
+        )
         .set_data(to_binary(&result)?);
 
     Ok(response)
 }
+
+pub fn prune_snapshots(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    before_height: u64,
+    start_after: Option<(u64, String)>,
+    limit: Option<u32>,
+) -> ExecuteResult {
+    let mut response = Response::new().add_attribute("action", "prune_snapshots");
+
+    let (pruned_count, next_start_after) =
+        prune_prepare_statuses(deps.storage, &env.block, before_height, start_after, limit)?;
+
+    response = response.add_attribute("pruned_count", pruned_count.to_string());
+    if let Some((height, address)) = next_start_after {
+        response = response
+            .add_attribute("next_start_after_height", height.to_string())
+            .add_attribute("next_start_after_address", address);
+    }
+
+    Ok(response)
+}
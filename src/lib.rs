@@ -1,5 +1,6 @@
 pub mod entrypoints;
 pub mod errors;
+pub mod merkle;
 pub mod msgs;
 
 mod executions;
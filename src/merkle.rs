@@ -0,0 +1,79 @@
+//! Merkle tree over addresses, shared by the contract and offline tooling.
+//!
+//! Leaves are `sha256(address)` and parents are `sha256(min(a, b) || max(a, b))`,
+//! so a proof is just the list of sibling hashes from the leaf to the root.
+//! A node without a sibling is carried up to the next level as is.
+
+use sha2::{Digest, Sha256};
+
+pub type Hash = [u8; 32];
+
+pub fn leaf_hash(address: &str) -> Hash {
+    Sha256::digest(address.as_bytes()).into()
+}
+
+fn parent_hash(a: &Hash, b: &Hash) -> Hash {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+fn next_level(level: &[Hash]) -> Vec<Hash> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [a, b] => parent_hash(a, b),
+            [a] => *a,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
+/// Returns `None` for an empty list
+pub fn build_root<T: AsRef<str>>(addresses: &[T]) -> Option<Hash> {
+    let mut level: Vec<Hash> = addresses.iter().map(|a| leaf_hash(a.as_ref())).collect();
+    while level.len() > 1 {
+        level = next_level(&level);
+    }
+
+    level.first().copied()
+}
+
+/// Returns `None` if the address is not in the list
+pub fn build_proof<T: AsRef<str>>(addresses: &[T], address: &str) -> Option<Vec<Hash>> {
+    let mut index = addresses.iter().position(|a| a.as_ref() == address)?;
+    let mut level: Vec<Hash> = addresses.iter().map(|a| leaf_hash(a.as_ref())).collect();
+
+    let mut proof = vec![];
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(index ^ 1) {
+            proof.push(*sibling);
+        }
+        level = next_level(&level);
+        index /= 2;
+    }
+
+    Some(proof)
+}
+
+pub fn verify(root: &Hash, address: &str, proof: &[Hash]) -> bool {
+    let computed = proof.iter().fold(leaf_hash(address), |hash, sibling| {
+        parent_hash(&hash, sibling)
+    });
+
+    &computed == root
+}
+
+pub fn to_hex(hash: &Hash) -> String {
+    hex::encode(hash)
+}
+
+pub fn from_hex(hash: &str) -> Option<Hash> {
+    let mut result: Hash = [0; 32];
+    hex::decode_to_slice(hash, &mut result).ok()?;
+
+    Some(result)
+}
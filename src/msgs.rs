@@ -1,27 +1,137 @@
-use cosmwasm_bignumber::Uint256;
-use cosmwasm_std::Uint128;
+use cosmwasm_bignumber::{Decimal256, Uint256};
+use cosmwasm_std::{attr, to_vec, Attribute, StdResult, Timestamp, Uint128};
+use cw0::{Duration, Expiration};
+use cw20::Denom;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use valkyrie_qualifier::{QualificationMsg, QualifiedContinueOption};
 
+use crate::states::{
+    AccessList, AllowlistPolicy, CampaignProfile, HolderPolicy, QualificationOutcome,
+    QualificationRecord, QualifierConfig, Quota, Requirement, Stats,
+};
+
+pub fn to_json_string<T: Serialize>(value: &T) -> StdResult<String> {
+    Ok(String::from_utf8(to_vec(value)?)?)
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
-    pub pool: String,
+    pub pools: Vec<PoolInfo>,
     pub gov: String,
-    pub deposit_delta: Uint256,
-    pub min_mine_stake_amount: Uint256,
+    pub requirement: Requirement,
     pub continue_option_on_fail: QualifiedContinueOption,
+    /// Continue option when only referrer conditions fail. Defaults to `continue_option_on_fail`.
+    pub continue_option_on_referrer_fail: Option<QualifiedContinueOption>,
+    pub prepare_validity: Option<Duration>,
+    pub campaign_restricted: Option<bool>,
+    pub snapshot_denoms: Option<Vec<Denom>>,
+    pub holder_policy: Option<HolderPolicy>,
+    pub allowlist_policy: Option<AllowlistPolicy>,
+    /// Requires `campaign_restricted`
+    pub participant_cap: Option<u64>,
+    pub start_time: Option<Expiration>,
+    pub end_time: Option<Expiration>,
+    /// Continue option outside of the window. Defaults to `continue_option_on_fail`.
+    pub continue_option_on_window_fail: Option<QualifiedContinueOption>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolInfo {
+    pub address: String,
+    /// Multiplier applied to the deposit of the pool. Defaults to one.
+    pub weight: Option<Decimal256>,
+}
+
+/// Fields left as `None` keep the current config
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct ConfigureMsg {
+    pub admin: Option<String>,
+    pub pools: Option<Vec<PoolInfo>>,
+    pub gov: Option<String>,
+    pub continue_option_on_fail: Option<QualifiedContinueOption>,
+    pub continue_option_on_referrer_fail: Option<ContinueOptionOverride>,
+    pub prepare_validity: Option<Duration>,
+    pub campaign_restricted: Option<bool>,
+    pub snapshot_denoms: Option<Vec<Denom>>,
+    pub holder_policy: Option<HolderPolicy>,
+    pub allowlist_policy: Option<AllowlistPolicy>,
+    /// `Never` removes the bound
+    pub start_time: Option<Expiration>,
+    /// `Never` removes the bound
+    pub end_time: Option<Expiration>,
+    pub continue_option_on_window_fail: Option<ContinueOptionOverride>,
+}
+
+/// Optional continue option of the config. `Unset` falls back to `continue_option_on_fail`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ContinueOptionOverride {
+    Eligible,
+    Ineligible,
+    Unset,
+}
+
+impl ContinueOptionOverride {
+    pub fn to_option(self) -> Option<QualifiedContinueOption> {
+        match self {
+            ContinueOptionOverride::Eligible => Some(QualifiedContinueOption::Eligible),
+            ContinueOptionOverride::Ineligible => Some(QualifiedContinueOption::Ineligible),
+            ContinueOptionOverride::Unset => None,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    Configure {
-        admin: Option<String>,
-        pool: Option<String>,
-        gov: Option<String>,
-        continue_option_on_fail: Option<QualifiedContinueOption>,
+    Configure(ConfigureMsg),
+    UpdateRequirement {
+        requirement: Requirement,
+    },
+    SetCampaignProfile {
+        campaign: String,
+        requirement: Requirement,
+        continue_option_on_fail: QualifiedContinueOption,
+        continue_option_on_referrer_fail: Option<QualifiedContinueOption>,
+    },
+    RemoveCampaignProfile {
+        campaign: String,
+    },
+    AddCampaign {
+        campaign: String,
+    },
+    RemoveCampaign {
+        campaign: String,
+    },
+    /// Sets the quota of the campaign, or the global participant cap without campaign.
+    /// `None` removes the limit. Requires `campaign_restricted`.
+    SetQuota {
+        campaign: Option<String>,
+        quota: Option<u64>,
+    },
+    AddMerkleRoot {
+        campaign: String,
+        root: String,
+    },
+    RemoveMerkleRoot {
+        campaign: String,
+        root: String,
+    },
+    /// Registers the sender to the merkle list of the campaign
+    RegisterMerkleProof {
+        campaign: String,
+        root: String,
+        proof: Vec<String>,
+    },
+    AddToAccessList {
+        list: AccessList,
+        addresses: Vec<String>,
+    },
+    RemoveFromAccessList {
+        list: AccessList,
+        addresses: Vec<String>,
     },
 
     // 1. Prepare
@@ -29,6 +139,479 @@ pub enum ExecuteMsg {
     // 2. Deposit
     // 3. Qualify
     Qualify(QualificationMsg),
+
+    PruneSnapshots {
+        before_height: u64,
+        /// (height, address) of the last snapshot inspected by the previous batch
+        start_after: Option<(u64, String)>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    // valkyrie qualifier interface
+    Qualify(QualificationMsg),
+    Requirement {},
+
+    Config {},
+    Evaluate(QualificationMsg),
+    PrepareStatus {
+        address: String,
+        height: Option<u64>,
+    },
+    State {},
+    Campaigns {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    CampaignProfile {
+        campaign: String,
+    },
+    CampaignProfiles {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    AccessList {
+        list: AccessList,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    RemainingSlots {
+        campaign: String,
+    },
+    MerkleRoots {
+        campaign: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    MerkleRegistration {
+        campaign: String,
+        address: String,
+    },
+    Stats {},
+    CampaignStats {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    QualificationsByActor {
+        actor: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    QualificationsByCampaign {
+        campaign: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    QualificationsByOutcome {
+        outcome: QualificationOutcome,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PrepareStatusResponse {
+    pub address: String,
+    pub height: u64,
+    pub pool_deposit: Uint256,
+    pub balances: Vec<(Denom, Uint128)>,
+    pub expiration: Expiration,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StateResponse {
+    pub config: QualifierConfig,
+    pub requirement: Requirement,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CampaignsResponse {
+    pub campaigns: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AccessListResponse {
+    pub list: AccessList,
+    pub addresses: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RemainingSlotsResponse {
+    pub global: Quota,
+    pub campaign: Quota,
+    /// `None` if unlimited
+    pub remaining: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MerkleRootsResponse {
+    pub campaign: String,
+    pub roots: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MerkleRegistrationResponse {
+    pub campaign: String,
+    pub address: String,
+    pub root: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StatsResponse {
+    pub global: Stats,
+    /// Failure reason kind and count
+    pub failure_reasons: Vec<(String, u64)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CampaignStatsResponse {
+    pub campaigns: Vec<(String, Stats)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QualificationRecordsResponse {
+    pub records: Vec<QualificationRecord>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CampaignProfileResponse {
+    pub campaign: String,
+    pub is_default: bool,
+    pub profile: CampaignProfile,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CampaignProfilesResponse {
+    pub profiles: Vec<CampaignProfileResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EvaluationResponse {
+    pub continue_option: QualifiedContinueOption,
+    pub checks: Vec<CheckResult>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CheckResult {
+    pub requirement: String,
+    pub satisfied: bool,
+    pub required: String,
+    pub actual: Option<String>,
+    pub failure_reason: Option<FailureReason>,
+}
+
+impl CheckResult {
+    pub fn pass(requirement: &str, required: impl ToString, actual: impl ToString) -> CheckResult {
+        CheckResult {
+            requirement: requirement.to_string(),
+            satisfied: true,
+            required: required.to_string(),
+            actual: Some(actual.to_string()),
+            failure_reason: None,
+        }
+    }
+
+    pub fn skip(requirement: &str, required: impl ToString) -> CheckResult {
+        CheckResult {
+            requirement: requirement.to_string(),
+            satisfied: true,
+            required: required.to_string(),
+            actual: None,
+            failure_reason: None,
+        }
+    }
+
+    /// Made by `skip`, which is the only check without an actual value
+    pub fn is_skipped(&self) -> bool {
+        self.satisfied && self.actual.is_none()
+    }
+
+    pub fn fail<T: ToString>(
+        requirement: &str,
+        required: impl ToString,
+        actual: Option<T>,
+        failure_reason: FailureReason,
+    ) -> CheckResult {
+        CheckResult {
+            requirement: requirement.to_string(),
+            satisfied: false,
+            required: required.to_string(),
+            actual: actual.map(|v| v.to_string()),
+            failure_reason: Some(failure_reason),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureReason {
+    NotStarted {
+        start_time: Expiration,
+    },
+    Ended {
+        end_time: Expiration,
+    },
+    Denylisted {
+        address: String,
+    },
+    QuotaExhausted {
+        quota: u64,
+        campaign: Option<String>,
+    },
+    SenderNotActor {},
+    PrepareStatusNotFound {},
+    DepositDecreased {
+        required: Uint256,
+        before: Uint256,
+        after: Uint256,
+    },
+    DepositDeltaTooLow {
+        required: Uint256,
+        actual: Uint256,
+    },
+    MineStakeTooLow {
+        required: Uint256,
+        actual: Uint256,
+    },
+    AlreadyParticipated {
+        participation_count: u64,
+    },
+    InCooldown {
+        last_participated_at: Timestamp,
+        eligible_at: Timestamp,
+    },
+    ReferrerNotFound {},
+    SelfReferral {},
+    ReferrerMineStakeTooLow {
+        required: Uint256,
+        actual: Uint256,
+    },
+    ReferrerNotParticipated {},
+    NotMerkleListed {},
+    ReferralCountExceeded {
+        max: u64,
+        actual: u64,
+    },
+    NegatedRequirementSatisfied {
+        requirement: String,
+    },
+    BalanceTooLow {
+        denom: String,
+        required: Uint128,
+        actual: Uint128,
+    },
+    DenomNotSnapshotted {
+        denom: String,
+    },
+    BalanceDecreased {
+        denom: String,
+        required: Uint128,
+        before: Uint128,
+        after: Uint128,
+    },
+    BalanceDeltaTooLow {
+        denom: String,
+        required: Uint128,
+        actual: Uint128,
+    },
+    VoteCountTooLow {
+        required: u64,
+        actual: u64,
+    },
+    NotVoted {
+        poll_id: u64,
+        required_vote: Option<VoteOption>,
+        actual_vote: Option<VoteOption>,
+    },
+    MineShareTooLow {
+        required: Uint128,
+        actual: Uint128,
+    },
+    MineShareRatioTooLow {
+        required: Decimal256,
+        actual: Decimal256,
+    },
+}
+
+impl FailureReason {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            FailureReason::NotStarted { .. } => "not_started",
+            FailureReason::Ended { .. } => "ended",
+            FailureReason::Denylisted { .. } => "denylisted",
+            FailureReason::QuotaExhausted { .. } => "quota_exhausted",
+            FailureReason::SenderNotActor {} => "sender_not_actor",
+            FailureReason::PrepareStatusNotFound { .. } => "prepare_status_not_found",
+            FailureReason::DepositDecreased { .. } => "deposit_decreased",
+            FailureReason::DepositDeltaTooLow { .. } => "deposit_delta_too_low",
+            FailureReason::MineStakeTooLow { .. } => "mine_stake_too_low",
+            FailureReason::AlreadyParticipated { .. } => "already_participated",
+            FailureReason::InCooldown { .. } => "in_cooldown",
+            FailureReason::ReferrerNotFound {} => "referrer_not_found",
+            FailureReason::SelfReferral {} => "self_referral",
+            FailureReason::ReferrerMineStakeTooLow { .. } => "referrer_mine_stake_too_low",
+            FailureReason::ReferrerNotParticipated {} => "referrer_not_participated",
+            FailureReason::NotMerkleListed {} => "not_merkle_listed",
+            FailureReason::ReferralCountExceeded { .. } => "referral_count_exceeded",
+            FailureReason::NegatedRequirementSatisfied { .. } => "negated_requirement_satisfied",
+            FailureReason::BalanceTooLow { .. } => "balance_too_low",
+            FailureReason::DenomNotSnapshotted { .. } => "denom_not_snapshotted",
+            FailureReason::BalanceDecreased { .. } => "balance_decreased",
+            FailureReason::BalanceDeltaTooLow { .. } => "balance_delta_too_low",
+            FailureReason::VoteCountTooLow { .. } => "vote_count_too_low",
+            FailureReason::NotVoted { .. } => "not_voted",
+            FailureReason::MineShareTooLow { .. } => "mine_share_too_low",
+            FailureReason::MineShareRatioTooLow { .. } => "mine_share_ratio_too_low",
+        }
+    }
+
+    pub fn to_json_string(&self) -> StdResult<String> {
+        to_json_string(self)
+    }
+
+    pub fn is_window_failure(&self) -> bool {
+        matches!(
+            self,
+            FailureReason::NotStarted { .. } | FailureReason::Ended { .. }
+        )
+    }
+
+    pub fn is_referrer_failure(&self) -> bool {
+        matches!(
+            self,
+            FailureReason::ReferrerNotFound {}
+                | FailureReason::SelfReferral {}
+                | FailureReason::ReferrerMineStakeTooLow { .. }
+                | FailureReason::ReferrerNotParticipated {}
+                | FailureReason::ReferralCountExceeded { .. }
+        )
+    }
+
+    pub fn attributes(&self) -> Vec<Attribute> {
+        let mut attributes = vec![attr("failure_reason", self.kind())];
+
+        match self {
+            FailureReason::NotStarted { start_time } => {
+                attributes.push(attr("start_time", start_time.to_string()));
+            }
+            FailureReason::Ended { end_time } => {
+                attributes.push(attr("end_time", end_time.to_string()));
+            }
+            FailureReason::Denylisted { address } => {
+                attributes.push(attr("address", address));
+            }
+            FailureReason::QuotaExhausted { quota, campaign } => {
+                attributes.push(attr("quota", quota.to_string()));
+                if let Some(campaign) = campaign {
+                    attributes.push(attr("campaign", campaign));
+                }
+            }
+            FailureReason::SenderNotActor {}
+            | FailureReason::PrepareStatusNotFound {}
+            | FailureReason::ReferrerNotFound {}
+            | FailureReason::SelfReferral {}
+            | FailureReason::ReferrerNotParticipated {}
+            | FailureReason::NotMerkleListed {} => {}
+            FailureReason::DepositDecreased {
+                required,
+                before,
+                after,
+            } => {
+                attributes.push(attr("required", required.to_string()));
+                attributes.push(attr("before", before.to_string()));
+                attributes.push(attr("after", after.to_string()));
+            }
+            FailureReason::DepositDeltaTooLow { required, actual }
+            | FailureReason::MineStakeTooLow { required, actual } => {
+                attributes.push(attr("required", required.to_string()));
+                attributes.push(attr("actual", actual.to_string()));
+            }
+            FailureReason::AlreadyParticipated {
+                participation_count,
+            } => {
+                attributes.push(attr("participation_count", participation_count.to_string()));
+            }
+            FailureReason::NegatedRequirementSatisfied { requirement } => {
+                attributes.push(attr("requirement", requirement));
+            }
+            FailureReason::DenomNotSnapshotted { denom } => {
+                attributes.push(attr("denom", denom));
+            }
+            FailureReason::BalanceDecreased {
+                denom,
+                required,
+                before,
+                after,
+            } => {
+                attributes.push(attr("denom", denom));
+                attributes.push(attr("required", required.to_string()));
+                attributes.push(attr("before", before.to_string()));
+                attributes.push(attr("after", after.to_string()));
+            }
+            FailureReason::BalanceTooLow {
+                denom,
+                required,
+                actual,
+            }
+            | FailureReason::BalanceDeltaTooLow {
+                denom,
+                required,
+                actual,
+            } => {
+                attributes.push(attr("denom", denom));
+                attributes.push(attr("required", required.to_string()));
+                attributes.push(attr("actual", actual.to_string()));
+            }
+            FailureReason::ReferrerMineStakeTooLow { required, actual } => {
+                attributes.push(attr("required", required.to_string()));
+                attributes.push(attr("actual", actual.to_string()));
+            }
+            FailureReason::ReferralCountExceeded { max, actual } => {
+                attributes.push(attr("max", max.to_string()));
+                attributes.push(attr("actual", actual.to_string()));
+            }
+            FailureReason::InCooldown {
+                last_participated_at,
+                eligible_at,
+            } => {
+                attributes.push(attr(
+                    "last_participated_at",
+                    last_participated_at.seconds().to_string(),
+                ));
+                attributes.push(attr("eligible_at", eligible_at.seconds().to_string()));
+            }
+            FailureReason::MineShareTooLow { required, actual } => {
+                attributes.push(attr("required", required.to_string()));
+                attributes.push(attr("actual", actual.to_string()));
+            }
+            FailureReason::MineShareRatioTooLow { required, actual } => {
+                attributes.push(attr("required", required.to_string()));
+                attributes.push(attr("actual", actual.to_string()));
+            }
+            FailureReason::VoteCountTooLow { required, actual } => {
+                attributes.push(attr("required", required.to_string()));
+                attributes.push(attr("actual", actual.to_string()));
+            }
+            FailureReason::NotVoted {
+                poll_id,
+                required_vote,
+                actual_vote,
+            } => {
+                attributes.push(attr("poll_id", poll_id.to_string()));
+                if let Some(v) = required_vote {
+                    attributes.push(attr("required_vote", v.to_string()));
+                }
+                if let Some(v) = actual_vote {
+                    attributes.push(attr("actual_vote", v.to_string()));
+                }
+            }
+        }
+
+        attributes
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -45,9 +628,17 @@ pub struct PoolBalanceOfResponse {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum GovQueryMsg {
+    State {},
     Staker { address: String },
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, JsonSchema)]
+pub struct GovStateResponse {
+    pub poll_count: u64,
+    pub total_share: Uint128,
+    pub total_deposit: Uint128,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
 pub struct GovStakerResponse {
     pub balance: Uint128,
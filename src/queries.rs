@@ -1,12 +1,23 @@
-use cosmwasm_std::{Deps, Env};
+use cosmwasm_std::{Addr, BlockInfo, Deps, Env, StdResult};
 use valkyrie_qualifier::{QualificationMsg, QualificationResult, QualifiedContinueOption};
 
 use crate::errors::ContractError;
-use crate::states::{QualifierConfig, Querier, Requirement};
+use crate::msgs::{
+    AccessListResponse, CampaignProfileResponse, CampaignProfilesResponse, CampaignStatsResponse,
+    CampaignsResponse, CheckResult, EvaluationResponse, FailureReason, MerkleRegistrationResponse,
+    MerkleRootsResponse, PrepareStatusResponse, QualificationRecordsResponse,
+    RemainingSlotsResponse, StateResponse, StatsResponse,
+};
+use crate::states::{
+    load_failure_reason_counts, load_latest_prepare_status, load_merkle_registration,
+    load_merkle_roots, load_prepare_status, load_qualification_records, load_registered_campaigns,
+    AccessList, AllowlistPolicy, CampaignProfile, HolderPolicy, QualificationContext,
+    QualificationOutcome, QualificationRecordFilter, QualifierConfig, Querier, Quota, Requirement,
+    Stats,
+};
 
 pub type QueryResult<T> = Result<T, ContractError>;
 
-#[allow(dead_code)]
 pub fn config(deps: Deps, _env: Env) -> QueryResult<QualifierConfig> {
     Ok(QualifierConfig::load(deps.storage)?)
 }
@@ -15,48 +26,252 @@ pub fn requirement(deps: Deps, _env: Env) -> QueryResult<Requirement> {
     Ok(Requirement::load(deps.storage)?)
 }
 
-pub fn qualify(deps: Deps, env: Env, msg: QualificationMsg) -> QueryResult<QualificationResult> {
-    let campaign = deps.api.addr_validate(msg.campaign.as_str())?;
-    let sender = deps.api.addr_validate(msg.sender.as_str())?;
-    let actor = deps.api.addr_validate(msg.actor.as_str())?;
-    let referrer = msg
-        .referrer
-        .map(|r| deps.api.addr_validate(r.as_str()))
-        .transpose()?;
+pub fn prepare_status(
+    deps: Deps,
+    _env: Env,
+    address: String,
+    height: Option<u64>,
+) -> QueryResult<Option<PrepareStatusResponse>> {
+    let address = deps.api.addr_validate(address.as_str())?;
+    let prepare_status = match height {
+        Some(height) => load_prepare_status(deps.storage, &height, &address)?,
+        None => load_latest_prepare_status(deps.storage, &address)?,
+    };
 
-    let requirement = Requirement::load(deps.storage)?;
-    let querier = Querier::new(&deps.querier);
+    Ok(prepare_status.map(|s| PrepareStatusResponse {
+        address: address.to_string(),
+        height: s.height,
+        pool_deposit: s.pool_deposit,
+        balances: s.balances,
+        expiration: s.expiration,
+    }))
+}
 
-    let (is_valid, error_msg) = requirement.is_satisfy_requirements(
-        deps.storage,
-        &env.block.height,
-        &querier,
-        &campaign,
-        &sender,
-        &actor,
-        referrer.as_ref(),
-    )?;
-
-    if is_valid {
-        Ok(QualificationResult {
-            continue_option: QualifiedContinueOption::Eligible,
-            reason: None,
-        })
-    } else {
-        let config = QualifierConfig::load(deps.storage)?;
+pub fn state(deps: Deps, _env: Env) -> QueryResult<StateResponse> {
+    Ok(StateResponse {
+        config: QualifierConfig::load(deps.storage)?,
+        requirement: Requirement::load(deps.storage)?,
+    })
+}
+
+pub fn campaigns(
+    deps: Deps,
+    _env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> QueryResult<CampaignsResponse> {
+    Ok(CampaignsResponse {
+        campaigns: load_registered_campaigns(deps.storage, start_after, limit)?,
+    })
+}
+
+pub fn access_list(
+    deps: Deps,
+    _env: Env,
+    list: AccessList,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> QueryResult<AccessListResponse> {
+    let addresses = list.load_range(deps.storage, start_after, limit)?;
+
+    Ok(AccessListResponse { list, addresses })
+}
+
+pub fn remaining_slots(
+    deps: Deps,
+    _env: Env,
+    campaign: String,
+) -> QueryResult<RemainingSlotsResponse> {
+    let campaign = deps.api.addr_validate(campaign.as_str())?;
+    let global = Quota::load_global(deps.storage)?;
+    let campaign = Quota::load_campaign(deps.storage, &campaign)?;
+
+    let remaining = match (global.remaining(), campaign.remaining()) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    };
+
+    Ok(RemainingSlotsResponse {
+        global,
+        campaign,
+        remaining,
+    })
+}
+
+pub fn merkle_roots(
+    deps: Deps,
+    _env: Env,
+    campaign: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> QueryResult<MerkleRootsResponse> {
+    let campaign = deps.api.addr_validate(campaign.as_str())?;
+
+    Ok(MerkleRootsResponse {
+        roots: load_merkle_roots(deps.storage, &campaign, start_after, limit)?,
+        campaign: campaign.to_string(),
+    })
+}
+
+pub fn merkle_registration(
+    deps: Deps,
+    _env: Env,
+    campaign: String,
+    address: String,
+) -> QueryResult<MerkleRegistrationResponse> {
+    let campaign = deps.api.addr_validate(campaign.as_str())?;
+    let address = deps.api.addr_validate(address.as_str())?;
+
+    Ok(MerkleRegistrationResponse {
+        root: load_merkle_registration(deps.storage, &campaign, &address)?,
+        campaign: campaign.to_string(),
+        address: address.to_string(),
+    })
+}
+
+pub fn stats(deps: Deps, _env: Env) -> QueryResult<StatsResponse> {
+    Ok(StatsResponse {
+        global: Stats::load(deps.storage)?,
+        failure_reasons: load_failure_reason_counts(deps.storage)?,
+    })
+}
+
+pub fn campaign_stats(
+    deps: Deps,
+    _env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> QueryResult<CampaignStatsResponse> {
+    Ok(CampaignStatsResponse {
+        campaigns: Stats::load_campaigns(deps.storage, start_after, limit)?,
+    })
+}
+
+pub fn qualifications_by_actor(
+    deps: Deps,
+    _env: Env,
+    actor: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> QueryResult<QualificationRecordsResponse> {
+    let filter = QualificationRecordFilter::Actor(deps.api.addr_validate(actor.as_str())?);
+
+    Ok(QualificationRecordsResponse {
+        records: load_qualification_records(deps.storage, &filter, start_after, limit)?,
+    })
+}
+
+pub fn qualifications_by_campaign(
+    deps: Deps,
+    _env: Env,
+    campaign: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> QueryResult<QualificationRecordsResponse> {
+    let filter = QualificationRecordFilter::Campaign(deps.api.addr_validate(campaign.as_str())?);
 
+    Ok(QualificationRecordsResponse {
+        records: load_qualification_records(deps.storage, &filter, start_after, limit)?,
+    })
+}
+
+pub fn qualifications_by_outcome(
+    deps: Deps,
+    _env: Env,
+    outcome: QualificationOutcome,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> QueryResult<QualificationRecordsResponse> {
+    let filter = QualificationRecordFilter::Outcome(outcome);
+
+    Ok(QualificationRecordsResponse {
+        records: load_qualification_records(deps.storage, &filter, start_after, limit)?,
+    })
+}
+
+pub struct Evaluation {
+    pub continue_option: QualifiedContinueOption,
+    pub failure_reason: Option<FailureReason>,
+    pub checks: Vec<CheckResult>,
+}
+
+impl Evaluation {
+    pub fn to_qualification_result(&self) -> QueryResult<QualificationResult> {
         Ok(QualificationResult {
-            continue_option: config.continue_option_on_fail,
-            reason: Some(error_msg),
+            continue_option: self.continue_option.clone(),
+            reason: self
+                .failure_reason
+                .as_ref()
+                .map(|r| r.to_json_string())
+                .transpose()?,
         })
     }
 }
 
+pub fn campaign_profile(
+    deps: Deps,
+    _env: Env,
+    campaign: String,
+) -> QueryResult<CampaignProfileResponse> {
+    let campaign = deps.api.addr_validate(campaign.as_str())?;
+    let profile = CampaignProfile::may_load(deps.storage, &campaign)?;
+
+    Ok(CampaignProfileResponse {
+        campaign: campaign.to_string(),
+        is_default: profile.is_none(),
+        profile: match profile {
+            Some(profile) => profile,
+            None => CampaignProfile::load_default(deps.storage)?,
+        },
+    })
+}
+
+pub fn campaign_profiles(
+    deps: Deps,
+    _env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> QueryResult<CampaignProfilesResponse> {
+    let profiles = CampaignProfile::load_range(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(campaign, profile)| CampaignProfileResponse {
+            campaign,
+            is_default: false,
+            profile,
+        })
+        .collect();
+
+    Ok(CampaignProfilesResponse { profiles })
+}
+
+pub fn qualify(deps: Deps, env: Env, msg: QualificationMsg) -> QueryResult<Evaluation> {
+    evaluate_requirement(deps, env, msg, true, true)
+}
+
 pub fn qualify_without_checking_deposit(
     deps: Deps,
     env: Env,
     msg: QualificationMsg,
 ) -> QueryResult<QualificationResult> {
+    evaluate_requirement(deps, env, msg, false, true)?.to_qualification_result()
+}
+
+pub fn evaluate(deps: Deps, env: Env, msg: QualificationMsg) -> QueryResult<EvaluationResponse> {
+    let evaluation = evaluate_requirement(deps, env, msg, true, false)?;
+
+    Ok(EvaluationResponse {
+        continue_option: evaluation.continue_option,
+        checks: evaluation.checks,
+    })
+}
+
+fn evaluate_requirement(
+    deps: Deps,
+    env: Env,
+    msg: QualificationMsg,
+    check_deposit: bool,
+    short_circuit: bool,
+) -> QueryResult<Evaluation> {
     let campaign = deps.api.addr_validate(msg.campaign.as_str())?;
     let sender = deps.api.addr_validate(msg.sender.as_str())?;
     let actor = deps.api.addr_validate(msg.actor.as_str())?;
@@ -65,30 +280,136 @@ pub fn qualify_without_checking_deposit(
         .map(|r| deps.api.addr_validate(r.as_str()))
         .transpose()?;
 
-    let requirement = Requirement::load(deps.storage)?;
+    let config = QualifierConfig::load(deps.storage)?;
+    let profile = CampaignProfile::load_or_default(deps.storage, &campaign)?;
     let querier = Querier::new(&deps.querier);
+    let allowlisted = AccessList::Allowlist.contains(deps.storage, &actor);
 
-    let (is_valid, error_msg) = requirement.is_satisfy_requirements_without_checking_deposit(
-        deps.storage,
-        &env.block.height,
-        &querier,
-        &campaign,
-        &sender,
-        &actor,
-        referrer.as_ref(),
-    )?;
-
-    if is_valid {
-        Ok(QualificationResult {
-            continue_option: QualifiedContinueOption::Eligible,
-            reason: None,
-        })
-    } else {
-        let config = QualifierConfig::load(deps.storage)?;
+    let context = QualificationContext {
+        storage: deps.storage,
+        block: &env.block,
+        querier: &querier,
+        config: &config,
+        campaign: &campaign,
+        sender: &sender,
+        actor: &actor,
+        referrer: referrer.as_ref(),
+        check_delta: check_deposit,
+        skip_stake_and_deposit: allowlisted
+            && config.allowlist_policy == AllowlistPolicy::SkipStakeAndDeposit,
+    };
 
-        Ok(QualificationResult {
-            continue_option: config.continue_option_on_fail,
-            reason: Some(error_msg),
-        })
+    let (failure_reason, checks) =
+        match check_policies(deps, &env.block, &config, &campaign, &sender, &actor)? {
+            Some(check) => (check.failure_reason.clone(), vec![check]),
+            None if allowlisted && config.allowlist_policy == AllowlistPolicy::Eligible => (
+                None,
+                vec![CheckResult::pass(
+                    "allowlist",
+                    "allowlisted",
+                    actor.as_str(),
+                )],
+            ),
+            None if short_circuit => (
+                profile.requirement.is_satisfy_requirements(&context)?,
+                vec![],
+            ),
+            None => profile.requirement.evaluate_requirements(&context)?,
+        };
+
+    let continue_option = match failure_reason {
+        None => QualifiedContinueOption::Eligible,
+        Some(ref r) if r.is_window_failure() => config
+            .continue_option_on_window_fail
+            .clone()
+            .unwrap_or(profile.continue_option_on_fail),
+        Some(ref r) if r.is_referrer_failure() => profile
+            .continue_option_on_referrer_fail
+            .unwrap_or(profile.continue_option_on_fail),
+        Some(_) => profile.continue_option_on_fail,
+    };
+
+    Ok(Evaluation {
+        continue_option,
+        failure_reason,
+        checks,
+    })
+}
+
+// evaluated before the requirement
+fn check_policies(
+    deps: Deps,
+    block: &BlockInfo,
+    config: &QualifierConfig,
+    campaign: &Addr,
+    sender: &Addr,
+    actor: &Addr,
+) -> StdResult<Option<CheckResult>> {
+    if let Some(start_time) = config.start_time {
+        if !start_time.is_expired(block) {
+            return Ok(Some(CheckResult::fail(
+                "window",
+                start_time,
+                None::<String>,
+                FailureReason::NotStarted { start_time },
+            )));
+        }
+    }
+    if let Some(end_time) = config.end_time {
+        if end_time.is_expired(block) {
+            return Ok(Some(CheckResult::fail(
+                "window",
+                end_time,
+                None::<String>,
+                FailureReason::Ended { end_time },
+            )));
+        }
+    }
+
+    for address in [sender, actor] {
+        if AccessList::Denylist.contains(deps.storage, address) {
+            return Ok(Some(CheckResult::fail(
+                "denylist",
+                "not denylisted",
+                Some(address),
+                FailureReason::Denylisted {
+                    address: address.to_string(),
+                },
+            )));
+        }
     }
+
+    if config.holder_policy == HolderPolicy::SameAddress && sender != actor {
+        return Ok(Some(CheckResult::fail(
+            "holder_policy",
+            actor,
+            Some(sender),
+            FailureReason::SenderNotActor {},
+        )));
+    }
+
+    for (quota, campaign) in [
+        (Quota::load_global(deps.storage)?, None),
+        (
+            Quota::load_campaign(deps.storage, campaign)?,
+            Some(campaign.to_string()),
+        ),
+    ] {
+        match quota.cap {
+            Some(cap) if quota.qualified_count >= cap => {
+                return Ok(Some(CheckResult::fail(
+                    "quota",
+                    cap,
+                    Some(quota.qualified_count),
+                    FailureReason::QuotaExhausted {
+                        quota: cap,
+                        campaign,
+                    },
+                )))
+            }
+            _ => {}
+        }
+    }
+
+    Ok(None)
 }
@@ -1,12 +1,21 @@
-use cosmwasm_bignumber::Uint256;
-use cosmwasm_std::{Addr, QuerierWrapper, StdResult, Storage, Uint128};
+use cosmwasm_bignumber::{Decimal256, Uint256};
+use cosmwasm_std::{
+    Addr, Api, BlockInfo, Order, QuerierWrapper, StdResult, Storage, Timestamp, Uint128,
+};
+use cw0::{Duration, Expiration};
 use cw20::{BalanceResponse, Cw20QueryMsg, Denom};
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{
+    Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex, PrimaryKey, U64Key,
+};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::borrow::Borrow;
 
-use crate::msgs::{GovQueryMsg, GovStakerResponse, PoolBalanceOfResponse, PoolQueryMsg};
+use crate::errors::ContractError;
+use crate::msgs::{
+    CheckResult, FailureReason, GovQueryMsg, GovStakerResponse, GovStateResponse,
+    PoolBalanceOfResponse, PoolQueryMsg, VoteOption,
+};
 use valkyrie::campaign::query_msgs::ActorResponse;
 use valkyrie_qualifier::QualifiedContinueOption;
 
@@ -15,9 +24,23 @@ const QUALIFIER_CONFIG: Item<QualifierConfig> = Item::new("qualifier_config");
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct QualifierConfig {
     pub admin: Addr,
-    pub pool: Addr,
+    pub pools: Vec<PoolConfig>,
     pub gov: Addr,
     pub continue_option_on_fail: QualifiedContinueOption,
+    pub continue_option_on_referrer_fail: Option<QualifiedContinueOption>,
+    pub prepare_validity: Duration,
+    pub campaign_restricted: bool,
+    pub snapshot_denoms: Vec<Denom>,
+    pub holder_policy: HolderPolicy,
+    pub allowlist_policy: AllowlistPolicy,
+    /// Maximum number of eligible qualifications across all campaigns
+    pub participant_cap: Option<u64>,
+    /// Qualifications are accepted from this point
+    pub start_time: Option<Expiration>,
+    /// Qualifications are rejected from this point
+    pub end_time: Option<Expiration>,
+    /// Continue option outside of the window. Defaults to `continue_option_on_fail`.
+    pub continue_option_on_window_fail: Option<QualifiedContinueOption>,
 }
 
 impl QualifierConfig {
@@ -34,43 +57,856 @@ impl QualifierConfig {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolConfig {
+    pub address: Addr,
+    pub weight: Decimal256,
+}
+
 #[allow(dead_code)]
 pub fn is_admin(storage: &dyn Storage, address: &Addr) -> StdResult<bool> {
     QualifierConfig::load(storage).map(|c| c.is_admin(address))
 }
 
-const USER_PREPARE_STATUS: Map<(&[u8], &str), Uint256> = Map::new("prepare_status");
+const REGISTERED_CAMPAIGNS: Map<&str, bool> = Map::new("registered_campaigns");
+
+pub fn register_campaign(storage: &mut dyn Storage, campaign: &Addr) -> StdResult<()> {
+    REGISTERED_CAMPAIGNS.save(storage, campaign.as_str(), &true)
+}
+
+pub fn deregister_campaign(storage: &mut dyn Storage, campaign: &Addr) {
+    REGISTERED_CAMPAIGNS.remove(storage, campaign.as_str())
+}
+
+pub fn is_registered_campaign(storage: &dyn Storage, campaign: &Addr) -> bool {
+    REGISTERED_CAMPAIGNS.has(storage, campaign.as_str())
+}
+
+pub fn load_registered_campaigns(
+    storage: &dyn Storage,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<String>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    REGISTERED_CAMPAIGNS
+        .keys(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|key| Ok(String::from_utf8(key)?))
+        .collect()
+}
+
+const ALLOWLIST: Map<&str, bool> = Map::new("allowlist");
+const DENYLIST: Map<&str, bool> = Map::new("denylist");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessList {
+    Allowlist,
+    Denylist,
+}
+
+impl AccessList {
+    fn map<'a>(&self) -> Map<'a, &'a str, bool> {
+        match self {
+            AccessList::Allowlist => ALLOWLIST,
+            AccessList::Denylist => DENYLIST,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            AccessList::Allowlist => "allowlist",
+            AccessList::Denylist => "denylist",
+        }
+    }
+
+    pub fn add(&self, storage: &mut dyn Storage, address: &Addr) -> StdResult<()> {
+        self.map().save(storage, address.as_str(), &true)
+    }
+
+    pub fn remove(&self, storage: &mut dyn Storage, address: &Addr) {
+        self.map().remove(storage, address.as_str())
+    }
+
+    pub fn contains(&self, storage: &dyn Storage, address: &Addr) -> bool {
+        self.map().has(storage, address.as_str())
+    }
+
+    pub fn load_range(
+        &self,
+        storage: &dyn Storage,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<String>> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        self.map()
+            .keys(storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|key| Ok(String::from_utf8(key)?))
+            .collect()
+    }
+}
+
+const MERKLE_ROOTS: Map<(&str, &str), bool> = Map::new("merkle_roots");
+const MERKLE_REGISTRATIONS: Map<(&str, &str), String> = Map::new("merkle_registrations");
+
+pub fn register_merkle_root(
+    storage: &mut dyn Storage,
+    campaign: &Addr,
+    root: &str,
+) -> StdResult<()> {
+    MERKLE_ROOTS.save(storage, (campaign.as_str(), root), &true)
+}
+
+pub fn deregister_merkle_root(storage: &mut dyn Storage, campaign: &Addr, root: &str) {
+    MERKLE_ROOTS.remove(storage, (campaign.as_str(), root))
+}
+
+pub fn has_merkle_root(storage: &dyn Storage, campaign: &Addr, root: &str) -> bool {
+    MERKLE_ROOTS.has(storage, (campaign.as_str(), root))
+}
+
+pub fn load_merkle_roots(
+    storage: &dyn Storage,
+    campaign: &Addr,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<String>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    MERKLE_ROOTS
+        .prefix(campaign.as_str())
+        .keys(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|key| Ok(String::from_utf8(key)?))
+        .collect()
+}
+
+pub fn save_merkle_registration(
+    storage: &mut dyn Storage,
+    campaign: &Addr,
+    address: &Addr,
+    root: &str,
+) -> StdResult<()> {
+    MERKLE_REGISTRATIONS.save(
+        storage,
+        (campaign.as_str(), address.as_str()),
+        &root.to_string(),
+    )
+}
+
+pub fn load_merkle_registration(
+    storage: &dyn Storage,
+    campaign: &Addr,
+    address: &Addr,
+) -> StdResult<Option<String>> {
+    MERKLE_REGISTRATIONS.may_load(storage, (campaign.as_str(), address.as_str()))
+}
+
+/// What an allowlisted actor is exempted from
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AllowlistPolicy {
+    /// Eligible without evaluating the requirement, participation conditions included
+    Eligible,
+    /// Only the stake and deposit conditions are skipped
+    #[default]
+    SkipStakeAndDeposit,
+}
+
+const QUALIFIED_COUNT: Item<u64> = Item::new("qualified_count");
+const CAMPAIGN_QUOTAS: Map<&str, u64> = Map::new("campaign_quotas");
+const CAMPAIGN_QUALIFIED_COUNTS: Map<&str, u64> = Map::new("campaign_qualified_counts");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Quota {
+    pub cap: Option<u64>,
+    pub qualified_count: u64,
+}
+
+impl Quota {
+    pub fn load_global(storage: &dyn Storage) -> StdResult<Quota> {
+        Ok(Quota {
+            cap: QualifierConfig::load(storage)?.participant_cap,
+            qualified_count: QUALIFIED_COUNT.may_load(storage)?.unwrap_or_default(),
+        })
+    }
+
+    pub fn load_campaign(storage: &dyn Storage, campaign: &Addr) -> StdResult<Quota> {
+        Ok(Quota {
+            cap: CAMPAIGN_QUOTAS.may_load(storage, campaign.as_str())?,
+            qualified_count: CAMPAIGN_QUALIFIED_COUNTS
+                .may_load(storage, campaign.as_str())?
+                .unwrap_or_default(),
+        })
+    }
+
+    /// `None` if uncapped
+    pub fn remaining(&self) -> Option<u64> {
+        self.cap.map(|cap| cap.saturating_sub(self.qualified_count))
+    }
+}
+
+pub fn save_campaign_quota(
+    storage: &mut dyn Storage,
+    campaign: &Addr,
+    quota: Option<u64>,
+) -> StdResult<()> {
+    match quota {
+        Some(quota) => CAMPAIGN_QUOTAS.save(storage, campaign.as_str(), &quota),
+        None => {
+            CAMPAIGN_QUOTAS.remove(storage, campaign.as_str());
+            Ok(())
+        }
+    }
+}
+
+pub fn has_campaign_quotas(storage: &dyn Storage) -> bool {
+    CAMPAIGN_QUOTAS
+        .keys(storage, None, None, Order::Ascending)
+        .next()
+        .is_some()
+}
+
+pub fn increase_qualified_count(storage: &mut dyn Storage, campaign: &Addr) -> StdResult<()> {
+    let count = QUALIFIED_COUNT.may_load(storage)?.unwrap_or_default();
+    QUALIFIED_COUNT.save(storage, &(count + 1))?;
+    CAMPAIGN_QUALIFIED_COUNTS.update(storage, campaign.as_str(), |count| -> StdResult<_> {
+        Ok(count.unwrap_or_default() + 1)
+    })?;
+
+    Ok(())
+}
+
+const QUALIFICATION_RECORD_SEQ: Item<u64> = Item::new("qualification_record_seq");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QualificationRecord {
+    pub id: u64,
+    pub campaign: Addr,
+    pub sender: Addr,
+    pub actor: Addr,
+    pub referrer: Option<Addr>,
+    pub height: u64,
+    pub time: Timestamp,
+    pub continue_option: QualifiedContinueOption,
+    pub reason: Option<FailureReason>,
+}
+
+/// Whether the requirement was met, whatever the continue option was
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QualificationOutcome {
+    Passed,
+    Failed,
+}
+
+impl QualificationOutcome {
+    fn key(&self) -> Vec<u8> {
+        match self {
+            QualificationOutcome::Passed => b"passed".to_vec(),
+            QualificationOutcome::Failed => b"failed".to_vec(),
+        }
+    }
+}
+
+impl QualificationRecord {
+    pub fn outcome(&self) -> QualificationOutcome {
+        match self.reason {
+            Some(_) => QualificationOutcome::Failed,
+            None => QualificationOutcome::Passed,
+        }
+    }
+}
+
+pub struct QualificationRecordIndexes<'a> {
+    pub actor: MultiIndex<'a, (Vec<u8>, Vec<u8>), QualificationRecord>,
+    pub campaign: MultiIndex<'a, (Vec<u8>, Vec<u8>), QualificationRecord>,
+    pub outcome: MultiIndex<'a, (Vec<u8>, Vec<u8>), QualificationRecord>,
+}
+
+impl<'a> IndexList<QualificationRecord> for QualificationRecordIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<QualificationRecord>> + '_> {
+        let indexes: Vec<&dyn Index<QualificationRecord>> =
+            vec![&self.actor, &self.campaign, &self.outcome];
+        Box::new(indexes.into_iter())
+    }
+}
+
+fn qualification_records<'a>(
+) -> IndexedMap<'a, U64Key, QualificationRecord, QualificationRecordIndexes<'a>> {
+    let indexes = QualificationRecordIndexes {
+        actor: MultiIndex::new(
+            |r, pk| (r.actor.as_bytes().to_vec(), pk),
+            "qualification_records",
+            "qualification_records__actor",
+        ),
+        campaign: MultiIndex::new(
+            |r, pk| (r.campaign.as_bytes().to_vec(), pk),
+            "qualification_records",
+            "qualification_records__campaign",
+        ),
+        outcome: MultiIndex::new(
+            |r, pk| (r.outcome().key(), pk),
+            "qualification_records",
+            "qualification_records__outcome",
+        ),
+    };
+
+    IndexedMap::new("qualification_records", indexes)
+}
+
+pub enum QualificationRecordFilter {
+    Actor(Addr),
+    Campaign(Addr),
+    Outcome(QualificationOutcome),
+}
+
+pub fn next_qualification_record_id(storage: &mut dyn Storage) -> StdResult<u64> {
+    let id = QUALIFICATION_RECORD_SEQ
+        .may_load(storage)?
+        .unwrap_or_default()
+        + 1;
+    QUALIFICATION_RECORD_SEQ.save(storage, &id)?;
+
+    Ok(id)
+}
+
+pub fn save_qualification_record(
+    storage: &mut dyn Storage,
+    record: &QualificationRecord,
+) -> StdResult<()> {
+    qualification_records().save(storage, U64Key::new(record.id), record)
+}
+
+/// Records are ordered by id, which is the order of the qualify calls
+pub fn load_qualification_records(
+    storage: &dyn Storage,
+    filter: &QualificationRecordFilter,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<QualificationRecord>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|id| Bound::exclusive(U64Key::new(id).joined_key()));
+
+    let records = qualification_records();
+    let prefix = match filter {
+        QualificationRecordFilter::Actor(actor) => {
+            records.idx.actor.prefix(actor.as_bytes().to_vec())
+        }
+        QualificationRecordFilter::Campaign(campaign) => {
+            records.idx.campaign.prefix(campaign.as_bytes().to_vec())
+        }
+        QualificationRecordFilter::Outcome(outcome) => records.idx.outcome.prefix(outcome.key()),
+    };
+
+    prefix
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| Ok(item?.1))
+        .collect()
+}
+
+const STATS: Item<Stats> = Item::new("stats");
+const CAMPAIGN_STATS: Map<&str, Stats> = Map::new("campaign_stats");
+const FAILURE_REASON_COUNTS: Map<&str, u64> = Map::new("failure_reason_counts");
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct Stats {
+    pub total_count: u64,
+    pub eligible_count: u64,
+    /// Calls with a failure reason, whatever the continue option was
+    pub failed_count: u64,
+    /// Sum of deposit increases since the snapshots consumed by the calls
+    pub total_deposit_delta: Uint256,
+}
+
+impl Stats {
+    pub fn load(storage: &dyn Storage) -> StdResult<Stats> {
+        Ok(STATS.may_load(storage)?.unwrap_or_default())
+    }
+
+    pub fn load_campaigns(
+        storage: &dyn Storage,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<(String, Stats)>> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        CAMPAIGN_STATS
+            .range(storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (campaign, stats) = item?;
+                Ok((String::from_utf8(campaign)?, stats))
+            })
+            .collect()
+    }
+
+    fn add(
+        &mut self,
+        continue_option: &QualifiedContinueOption,
+        failed: bool,
+        deposit_delta: Uint256,
+    ) {
+        self.total_count += 1;
+        if continue_option == &QualifiedContinueOption::Eligible {
+            self.eligible_count += 1;
+        }
+        if failed {
+            self.failed_count += 1;
+        }
+        self.total_deposit_delta += deposit_delta;
+    }
+}
+
+pub fn load_failure_reason_counts(storage: &dyn Storage) -> StdResult<Vec<(String, u64)>> {
+    FAILURE_REASON_COUNTS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (kind, count) = item?;
+            Ok((String::from_utf8(kind)?, count))
+        })
+        .collect()
+}
+
+pub fn update_stats(
+    storage: &mut dyn Storage,
+    campaign: &Addr,
+    continue_option: &QualifiedContinueOption,
+    failure_reason: Option<&FailureReason>,
+    deposit_delta: Uint256,
+) -> StdResult<()> {
+    let failed = failure_reason.is_some();
+
+    let mut stats = Stats::load(storage)?;
+    stats.add(continue_option, failed, deposit_delta);
+    STATS.save(storage, &stats)?;
+
+    CAMPAIGN_STATS.update(storage, campaign.as_str(), |stats| -> StdResult<_> {
+        let mut stats = stats.unwrap_or_default();
+        stats.add(continue_option, failed, deposit_delta);
+        Ok(stats)
+    })?;
+
+    if let Some(failure_reason) = failure_reason {
+        FAILURE_REASON_COUNTS.update(storage, failure_reason.kind(), |count| -> StdResult<_> {
+            Ok(count.unwrap_or_default() + 1)
+        })?;
+    }
+
+    Ok(())
+}
+
+pub const DEFAULT_LIMIT: u32 = 10;
+pub const MAX_LIMIT: u32 = 30;
+
+const USER_PREPARE_STATUS: Map<(&[u8], &str), PrepareStatus> = Map::new("prepare_status");
+const USER_LATEST_PREPARE_HEIGHT: Map<&str, u64> = Map::new("latest_prepare_height");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PrepareStatus {
+    pub height: u64,
+    pub pool_deposit: Uint256,
+    #[serde(default)]
+    pub balances: Vec<(Denom, Uint128)>,
+    pub expiration: Expiration,
+}
+
+impl PrepareStatus {
+    pub fn is_valid(&self, block: &BlockInfo) -> bool {
+        !self.expiration.is_expired(block)
+    }
+
+    pub fn balance_of(&self, denom: &Denom) -> Option<Uint128> {
+        self.balances
+            .iter()
+            .find(|(d, _)| d == denom)
+            .map(|(_, amount)| *amount)
+    }
+}
 
 pub fn save_prepare_status(
     storage: &mut dyn Storage,
-    block_number: &u64,
     address: &Addr,
-    amount: &Uint256,
+    prepare_status: &PrepareStatus,
 ) -> StdResult<()> {
     USER_PREPARE_STATUS.borrow().save(
         storage,
-        (&block_number.to_be_bytes(), address.as_str()),
-        amount,
-    )
+        (&prepare_status.height.to_be_bytes(), address.as_str()),
+        prepare_status,
+    )?;
+
+    // only the latest snapshot is used, so drop the previous one of the address
+    let prev_height = USER_LATEST_PREPARE_HEIGHT.may_load(storage, address.as_str())?;
+    if let Some(prev_height) = prev_height.filter(|h| *h != prepare_status.height) {
+        USER_PREPARE_STATUS
+            .borrow()
+            .remove(storage, (&prev_height.to_be_bytes(), address.as_str()));
+    }
+
+    USER_LATEST_PREPARE_HEIGHT.save(storage, address.as_str(), &prepare_status.height)
+}
+
+pub fn remove_prepare_status(
+    storage: &mut dyn Storage,
+    block_number: &u64,
+    address: &Addr,
+) -> StdResult<()> {
+    USER_PREPARE_STATUS
+        .borrow()
+        .remove(storage, (&block_number.to_be_bytes(), address.as_str()));
+
+    let latest_height = USER_LATEST_PREPARE_HEIGHT.may_load(storage, address.as_str())?;
+    if latest_height == Some(*block_number) {
+        USER_LATEST_PREPARE_HEIGHT.remove(storage, address.as_str());
+    }
+
+    Ok(())
+}
+
+/// Removes expired snapshots prepared before `before_height`.
+/// At most `limit` snapshots are inspected, so the caller can prune in bounded batches.
+/// Still valid snapshots are skipped but counted, and the key of the last inspected one is
+/// returned as the cursor of the next batch if there may be more.
+pub fn prune_prepare_statuses(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    before_height: u64,
+    start_after: Option<(u64, String)>,
+    limit: Option<u32>,
+) -> StdResult<(u64, Option<(u64, String)>)> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let min = start_after.map(|(height, address)| {
+        Bound::exclusive((height.to_be_bytes().as_ref(), address.as_str()).joined_key())
+    });
+    let max = Bound::exclusive((before_height.to_be_bytes().as_ref(), "").joined_key());
+
+    let inspected = USER_PREPARE_STATUS
+        .borrow()
+        .range(storage, min, Some(max), Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (key, prepare_status) = item?;
+            let address = String::from_utf8(key[2 + 8..].to_vec())?;
+            Ok((address, prepare_status))
+        })
+        .collect::<StdResult<Vec<(String, PrepareStatus)>>>()?;
+
+    let mut pruned_count = 0u64;
+    for (address, prepare_status) in inspected.iter() {
+        if !prepare_status.is_valid(block) {
+            remove_prepare_status(storage, &prepare_status.height, &Addr::unchecked(address))?;
+            pruned_count += 1;
+        }
+    }
+
+    let next_start_after = match inspected.last() {
+        Some((address, prepare_status)) if inspected.len() == limit => {
+            Some((prepare_status.height, address.clone()))
+        }
+        _ => None,
+    };
+
+    Ok((pruned_count, next_start_after))
 }
 
 pub fn load_prepare_status(
     storage: &dyn Storage,
     block_number: &u64,
     address: &Addr,
-) -> StdResult<Uint256> {
-    Ok(USER_PREPARE_STATUS
+) -> StdResult<Option<PrepareStatus>> {
+    USER_PREPARE_STATUS
         .borrow()
-        .may_load(storage, (&block_number.to_be_bytes(), address.as_str()))?
-        .unwrap_or_default())
+        .may_load(storage, (&block_number.to_be_bytes(), address.as_str()))
+}
+
+pub fn load_latest_prepare_status(
+    storage: &dyn Storage,
+    address: &Addr,
+) -> StdResult<Option<PrepareStatus>> {
+    match USER_LATEST_PREPARE_HEIGHT.may_load(storage, address.as_str())? {
+        Some(height) => load_prepare_status(storage, &height, address),
+        None => Ok(None),
+    }
+}
+
+/// Returns the latest snapshot of the address if it is not expired at the given block.
+pub fn load_valid_prepare_status(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    address: &Addr,
+) -> StdResult<Option<PrepareStatus>> {
+    Ok(load_latest_prepare_status(storage, address)?.filter(|s| s.is_valid(block)))
 }
 
 const REQUIREMENT: Item<Requirement> = Item::new("requirement");
 
+const MAX_REQUIREMENT_DEPTH: usize = 8;
+const MAX_REQUIREMENT_NODES: usize = 32;
+// 100 years, far enough from the overflow of `Timestamp::plus_seconds` in nanoseconds
+const MAX_COOLDOWN_SECONDS: u64 = 100 * 365 * 24 * 60 * 60;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Requirement {
+    And(Vec<Requirement>),
+    Or(Vec<Requirement>),
+    Not(Box<Requirement>),
+    Condition(Condition),
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct Requirement {
-    pub deposit_delta: Uint256,
-    pub min_mine_stake_amount: Uint256,
+#[serde(rename_all = "snake_case")]
+pub enum Condition {
+    DepositDelta {
+        min: Uint256,
+    },
+    MineStake {
+        min: Uint256,
+        #[serde(default)]
+        mode: StakeMode,
+    },
+    /// Maximum participation count including this one, so `1` only allows actors who never participated
+    ParticipationCount {
+        max: u64,
+    },
+    /// Seconds the actor must wait since the last participation, up to 100 years
+    ParticipationCooldown {
+        seconds: u64,
+    },
+    Balance {
+        denom: Denom,
+        min: Uint128,
+        #[serde(default)]
+        holder: Holder,
+    },
+    /// Gov share of the sender, which does not drift as staking rewards accrue
+    MineShare {
+        min: ShareThreshold,
+    },
+    /// Referrer is neither the actor nor the sender
+    ReferrerNotSelf {},
+    ReferrerMineStake {
+        min: Uint256,
+        #[serde(default)]
+        mode: StakeMode,
+    },
+    /// Referrer has participated in the campaign
+    ReferrerParticipated {},
+    /// Maximum referral count of the referrer including this referral
+    ReferralCount {
+        max: u64,
+    },
+    /// Actor has registered a merkle proof against a root of the campaign
+    MerkleListed {},
+    /// Number of polls the sender has voted in
+    VoteCount {
+        min: u64,
+    },
+    /// Sender has voted in the poll, with the vote option if given
+    Voted {
+        poll_id: u64,
+        #[serde(default)]
+        vote: Option<VoteOption>,
+    },
+    /// Balance increase of the sender since prepare. The denom must be one of `snapshot_denoms`.
+    BalanceDelta {
+        denom: Denom,
+        min: Uint128,
+    },
+}
+
+/// Whose balances, stake and deposit are checked
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HolderPolicy {
+    #[default]
+    Sender,
+    /// Sender must be the actor
+    SameAddress,
+    Actor,
+    Both,
+}
+
+impl HolderPolicy {
+    pub fn holders<'a>(&self, sender: &'a Addr, actor: &'a Addr) -> Vec<&'a Addr> {
+        match self {
+            HolderPolicy::Sender | HolderPolicy::SameAddress => vec![sender],
+            HolderPolicy::Actor => vec![actor],
+            HolderPolicy::Both if sender == actor => vec![sender],
+            HolderPolicy::Both => vec![sender, actor],
+        }
+    }
+}
+
+/// `Sender` follows the holder policy of the config
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Holder {
+    #[default]
+    Sender,
+    Actor,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ShareThreshold {
+    Amount(Uint128),
+    /// Fraction of the total share of the gov contract
+    Ratio(Decimal256),
+}
+
+/// Which part of the gov stake is counted
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StakeMode {
+    #[default]
+    Total,
+    /// Stake not locked in any vote
+    Unlocked,
+    /// Stake locked in votes
+    Locked,
+}
+
+impl StakeMode {
+    pub fn stake_amount(&self, staker: &GovStakerResponse) -> Uint128 {
+        // the same tokens can be locked in multiple polls, so the locked amount is the largest one
+        let locked_amount = staker
+            .locked_balance
+            .iter()
+            .map(|(_, voter_info)| voter_info.balance)
+            .max()
+            .unwrap_or_default()
+            .min(staker.balance);
+
+        match self {
+            StakeMode::Total => staker.balance,
+            StakeMode::Unlocked => staker.balance - locked_amount,
+            StakeMode::Locked => locked_amount,
+        }
+    }
+}
+
+const CAMPAIGN_PROFILES: Map<&str, CampaignProfile> = Map::new("campaign_profiles");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CampaignProfile {
+    pub requirement: Requirement,
+    pub continue_option_on_fail: QualifiedContinueOption,
+    #[serde(default)]
+    pub continue_option_on_referrer_fail: Option<QualifiedContinueOption>,
+}
+
+impl CampaignProfile {
+    pub fn save(&self, storage: &mut dyn Storage, campaign: &Addr) -> StdResult<()> {
+        CAMPAIGN_PROFILES.save(storage, campaign.as_str(), self)
+    }
+
+    pub fn remove(storage: &mut dyn Storage, campaign: &Addr) {
+        CAMPAIGN_PROFILES.remove(storage, campaign.as_str())
+    }
+
+    pub fn may_load(storage: &dyn Storage, campaign: &Addr) -> StdResult<Option<CampaignProfile>> {
+        CAMPAIGN_PROFILES.may_load(storage, campaign.as_str())
+    }
+
+    /// Profile made of the default requirement and config, used for campaigns without own profile.
+    pub fn load_default(storage: &dyn Storage) -> StdResult<CampaignProfile> {
+        let config = QualifierConfig::load(storage)?;
+
+        Ok(CampaignProfile {
+            requirement: Requirement::load(storage)?,
+            continue_option_on_fail: config.continue_option_on_fail,
+            continue_option_on_referrer_fail: config.continue_option_on_referrer_fail,
+        })
+    }
+
+    pub fn load_or_default(storage: &dyn Storage, campaign: &Addr) -> StdResult<CampaignProfile> {
+        match CampaignProfile::may_load(storage, campaign)? {
+            Some(profile) => Ok(profile),
+            None => CampaignProfile::load_default(storage),
+        }
+    }
+
+    pub fn load_all(storage: &dyn Storage) -> StdResult<Vec<CampaignProfile>> {
+        CAMPAIGN_PROFILES
+            .range(storage, None, None, Order::Ascending)
+            .map(|item| Ok(item?.1))
+            .collect()
+    }
+
+    pub fn load_range(
+        storage: &dyn Storage,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<(String, CampaignProfile)>> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        CAMPAIGN_PROFILES
+            .range(storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (campaign, profile) = item?;
+                Ok((String::from_utf8(campaign)?, profile))
+            })
+            .collect()
+    }
+}
+
+pub struct QualificationContext<'a> {
+    pub storage: &'a dyn Storage,
+    pub block: &'a BlockInfo,
+    pub querier: &'a Querier<'a>,
+    pub config: &'a QualifierConfig,
+    pub campaign: &'a Addr,
+    pub sender: &'a Addr,
+    pub actor: &'a Addr,
+    pub referrer: Option<&'a Addr>,
+    /// Whether to check the conditions comparing with the prepare snapshot
+    pub check_delta: bool,
+    /// Whether to skip the stake and deposit conditions
+    pub skip_stake_and_deposit: bool,
+}
+
+impl<'a> QualificationContext<'a> {
+    pub fn holders(&self) -> Vec<&'a Addr> {
+        self.config.holder_policy.holders(self.sender, self.actor)
+    }
+}
+
+/// Result of a requirement node. A skipped node was not checked at all,
+/// so it does not fail a group but cannot be negated either.
+#[derive(Clone, Debug, PartialEq)]
+enum Outcome {
+    Satisfied,
+    Skipped,
+    Failed(FailureReason),
+}
+
+impl Outcome {
+    fn failure_reason(self) -> Option<FailureReason> {
+        match self {
+            Outcome::Failed(reason) => Some(reason),
+            Outcome::Satisfied | Outcome::Skipped => None,
+        }
+    }
+}
+
+// referrer failures are reported only when nothing else failed,
+// so the referrer continue option never covers an actor failure
+fn decisive_failure(current: Option<FailureReason>, reason: FailureReason) -> FailureReason {
+    match current {
+        Some(current) if !current.is_referrer_failure() || reason.is_referrer_failure() => current,
+        _ => reason,
+    }
 }
 
 impl Requirement {
@@ -82,118 +918,793 @@ impl Requirement {
         REQUIREMENT.load(storage)
     }
 
-    #[allow(clippy::too_many_arguments)]
-    pub fn is_satisfy_requirements(
-        &self,
-        storage: &dyn Storage,
-        block_number: &u64,
-        querier: &Querier,
-        campaign: &Addr,
-        sender: &Addr,
-        actor: &Addr,
-        _referrer: Option<&Addr>,
-    ) -> StdResult<(bool, String)> {
-        let result = self.is_satisfy_deposit_delta(storage, querier, block_number, sender)?;
-        if !result.0 {
-            return Ok(result);
+    pub fn name(&self) -> &'static str {
+        match self {
+            Requirement::And(_) => "and",
+            Requirement::Or(_) => "or",
+            Requirement::Not(_) => "not",
+            Requirement::Condition(condition) => condition.name(),
         }
+    }
+
+    pub fn validate(&self, api: &dyn Api, snapshot_denoms: &[Denom]) -> Result<(), ContractError> {
+        let mut node_count = 0usize;
+        self.validate_node(api, snapshot_denoms, 1, &mut node_count)
+    }
 
-        let result = self.is_satisfy_mine_stake_amount(storage, querier, sender)?;
-        if !result.0 {
-            return Ok(result);
+    fn validate_node(
+        &self,
+        api: &dyn Api,
+        snapshot_denoms: &[Denom],
+        depth: usize,
+        node_count: &mut usize,
+    ) -> Result<(), ContractError> {
+        *node_count += 1;
+        if depth > MAX_REQUIREMENT_DEPTH || *node_count > MAX_REQUIREMENT_NODES {
+            return Err(ContractError::InvalidArgument {
+                msg: "Requirement is too complex".to_string(),
+            });
         }
 
-        let result = self.is_satisfy_participation_count(querier, campaign, actor)?;
-        if !result.0 {
-            return Ok(result);
+        match self {
+            Requirement::And(children) | Requirement::Or(children) => {
+                if children.is_empty() {
+                    return Err(ContractError::InvalidArgument {
+                        msg: format!("Empty {} requirement", self.name()),
+                    });
+                }
+
+                children
+                    .iter()
+                    .try_for_each(|c| c.validate_node(api, snapshot_denoms, depth + 1, node_count))
+            }
+            Requirement::Not(child) => {
+                child.validate_node(api, snapshot_denoms, depth + 1, node_count)
+            }
+            Requirement::Condition(condition) => condition.validate(api, snapshot_denoms),
         }
+    }
 
-        Ok((true, String::default()))
+    /// Stops at the first decisive check and returns the failure reason if not satisfied.
+    pub fn is_satisfy_requirements(
+        &self,
+        context: &QualificationContext,
+    ) -> StdResult<Option<FailureReason>> {
+        Ok(self.evaluate(context, true, &mut vec![])?.failure_reason())
     }
 
-    #[allow(clippy::too_many_arguments)]
-    pub fn is_satisfy_requirements_without_checking_deposit(
+    /// Runs every check without short-circuiting, so the caller can see all failing checks at once.
+    pub fn evaluate_requirements(
         &self,
-        storage: &dyn Storage,
-        _block_number: &u64,
-        querier: &Querier,
-        campaign: &Addr,
-        sender: &Addr,
-        actor: &Addr,
-        _referrer: Option<&Addr>,
-    ) -> StdResult<(bool, String)> {
-        let result = self.is_satisfy_mine_stake_amount(storage, querier, sender)?;
-        if !result.0 {
-            return Ok(result);
+        context: &QualificationContext,
+    ) -> StdResult<(Option<FailureReason>, Vec<CheckResult>)> {
+        let mut checks = vec![];
+        let failure_reason = self.evaluate(context, false, &mut checks)?.failure_reason();
+
+        Ok((failure_reason, checks))
+    }
+
+    fn evaluate(
+        &self,
+        context: &QualificationContext,
+        short_circuit: bool,
+        checks: &mut Vec<CheckResult>,
+    ) -> StdResult<Outcome> {
+        match self {
+            Requirement::And(children) => {
+                let mut satisfied = false;
+                let mut failure_reason = None;
+                for child in children.iter() {
+                    match child.evaluate(context, short_circuit, checks)? {
+                        Outcome::Satisfied => satisfied = true,
+                        Outcome::Skipped => {}
+                        Outcome::Failed(reason) => {
+                            failure_reason = Some(decisive_failure(failure_reason, reason));
+                        }
+                    }
+                    // go on past referrer failures to see whether the actor fails as well
+                    if short_circuit
+                        && matches!(&failure_reason, Some(r) if !r.is_referrer_failure())
+                    {
+                        break;
+                    }
+                }
+
+                Ok(match failure_reason {
+                    Some(reason) => Outcome::Failed(reason),
+                    None if satisfied => Outcome::Satisfied,
+                    None => Outcome::Skipped,
+                })
+            }
+            Requirement::Or(children) => {
+                let mut satisfied = false;
+                let mut skipped = false;
+                let mut failure_reason = None;
+                for child in children.iter() {
+                    match child.evaluate(context, short_circuit, checks)? {
+                        Outcome::Satisfied => satisfied = true,
+                        Outcome::Skipped => skipped = true,
+                        Outcome::Failed(reason) => {
+                            failure_reason = Some(decisive_failure(failure_reason, reason));
+                        }
+                    }
+                    if satisfied && short_circuit {
+                        break;
+                    }
+                }
+
+                Ok(match failure_reason {
+                    _ if satisfied => Outcome::Satisfied,
+                    Some(reason) if !skipped => Outcome::Failed(reason),
+                    _ => Outcome::Skipped,
+                })
+            }
+            Requirement::Not(child) => {
+                let required = format!("not {}", child.name());
+                // the checks of the child tell the opposite, so the node reports its own result
+                let check = match child.evaluate(context, short_circuit, checks)? {
+                    Outcome::Satisfied => CheckResult::fail(
+                        self.name(),
+                        required,
+                        Some("satisfied"),
+                        FailureReason::NegatedRequirementSatisfied {
+                            requirement: child.name().to_string(),
+                        },
+                    ),
+                    // a skipped check is neither satisfied nor failed, so there is nothing to negate
+                    Outcome::Skipped => return Ok(Outcome::Skipped),
+                    Outcome::Failed(_) => CheckResult::pass(self.name(), required, "not satisfied"),
+                };
+                let outcome = match &check.failure_reason {
+                    Some(reason) => Outcome::Failed(reason.clone()),
+                    None => Outcome::Satisfied,
+                };
+                checks.push(check);
+
+                Ok(outcome)
+            }
+            Requirement::Condition(condition) => {
+                let check = condition.evaluate(context)?;
+                let outcome = match &check.failure_reason {
+                    Some(reason) => Outcome::Failed(reason.clone()),
+                    None if check.is_skipped() => Outcome::Skipped,
+                    None => Outcome::Satisfied,
+                };
+                checks.push(check);
+
+                Ok(outcome)
+            }
         }
+    }
+}
+
+impl Condition {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Condition::DepositDelta { .. } => "deposit_delta",
+            Condition::MineStake { .. } => "mine_stake",
+            Condition::ParticipationCount { .. } => "participation_count",
+            Condition::ParticipationCooldown { .. } => "participation_cooldown",
+            Condition::Balance { .. } => "balance",
+            Condition::BalanceDelta { .. } => "balance_delta",
+            Condition::MineShare { .. } => "mine_share",
+            Condition::ReferrerNotSelf {} => "referrer_not_self",
+            Condition::ReferrerMineStake { .. } => "referrer_mine_stake",
+            Condition::ReferrerParticipated {} => "referrer_participated",
+            Condition::ReferralCount { .. } => "referral_count",
+            Condition::MerkleListed {} => "merkle_listed",
+            Condition::VoteCount { .. } => "vote_count",
+            Condition::Voted { .. } => "voted",
+        }
+    }
+
+    fn validate(&self, api: &dyn Api, snapshot_denoms: &[Denom]) -> Result<(), ContractError> {
+        match self {
+            Condition::Balance { denom, .. } => validate_denom(api, denom),
+            Condition::BalanceDelta { denom, .. } => {
+                validate_denom(api, denom)?;
+                if !snapshot_denoms.contains(denom) {
+                    return Err(ContractError::InvalidArgument {
+                        msg: format!("Denom is not snapshotted: {}", denom_to_string(denom)),
+                    });
+                }
 
-        let result = self.is_satisfy_participation_count(querier, campaign, actor)?;
-        if !result.0 {
-            return Ok(result);
+                Ok(())
+            }
+            Condition::ParticipationCooldown { seconds } if *seconds > MAX_COOLDOWN_SECONDS => {
+                Err(ContractError::InvalidArgument {
+                    msg: format!("Cooldown must not exceed {} seconds", MAX_COOLDOWN_SECONDS),
+                })
+            }
+            Condition::MineShare {
+                min: ShareThreshold::Ratio(ratio),
+            } if *ratio > Decimal256::one() => Err(ContractError::InvalidArgument {
+                msg: "Share ratio must not exceed 1".to_string(),
+            }),
+            _ => Ok(()),
         }
+    }
+
+    fn is_stake_or_deposit(&self) -> bool {
+        matches!(
+            self,
+            Condition::DepositDelta { .. }
+                | Condition::BalanceDelta { .. }
+                | Condition::MineStake { .. }
+                | Condition::MineShare { .. }
+        )
+    }
 
-        Ok((true, String::default()))
+    fn evaluate(&self, context: &QualificationContext) -> StdResult<CheckResult> {
+        if context.skip_stake_and_deposit && self.is_stake_or_deposit() {
+            return Ok(CheckResult::skip(self.name(), "allowlisted"));
+        }
+
+        match self {
+            Condition::DepositDelta { min } => evaluate_on_holders(context, |holder| {
+                self.is_satisfy_deposit_delta(context, holder, min)
+            }),
+            Condition::MineStake { min, mode } => evaluate_on_holders(context, |holder| {
+                self.is_satisfy_mine_stake_amount(context, holder, min, mode)
+            }),
+            Condition::ParticipationCount { max } => {
+                self.is_satisfy_participation_count(context, max)
+            }
+            Condition::ParticipationCooldown { seconds } => {
+                self.is_satisfy_participation_cooldown(context, seconds)
+            }
+            Condition::Balance {
+                denom,
+                min,
+                holder: Holder::Sender,
+            } => evaluate_on_holders(context, |holder| {
+                self.is_satisfy_balance(context, holder, denom, min)
+            }),
+            Condition::Balance {
+                denom,
+                min,
+                holder: Holder::Actor,
+            } => self.is_satisfy_balance(context, context.actor, denom, min),
+            Condition::BalanceDelta { denom, min } => evaluate_on_holders(context, |holder| {
+                self.is_satisfy_balance_delta(context, holder, denom, min)
+            }),
+            Condition::MineShare { min } => evaluate_on_holders(context, |holder| {
+                self.is_satisfy_mine_share(context, holder, min)
+            }),
+            Condition::ReferrerNotSelf {} => self.is_satisfy_referrer_not_self(context),
+            Condition::ReferrerMineStake { min, mode } => {
+                self.is_satisfy_referrer_mine_stake_amount(context, min, mode)
+            }
+            Condition::ReferrerParticipated {} => self.is_satisfy_referrer_participated(context),
+            Condition::ReferralCount { max } => self.is_satisfy_referral_count(context, max),
+            Condition::MerkleListed {} => self.is_satisfy_merkle_listed(context),
+            Condition::VoteCount { min } => evaluate_on_holders(context, |holder| {
+                self.is_satisfy_vote_count(context, holder, min)
+            }),
+            Condition::Voted { poll_id, vote } => evaluate_on_holders(context, |holder| {
+                self.is_satisfy_voted(context, holder, poll_id, vote)
+            }),
+        }
     }
 
     fn is_satisfy_deposit_delta(
         &self,
-        storage: &dyn Storage,
-        querier: &Querier,
-        block_number: &u64,
-        sender: &Addr,
-    ) -> StdResult<(bool, String)> {
-        // it's because before amount is zero
-        let config = QualifierConfig::load(storage)?;
-        let prepare_status = load_prepare_status(storage, block_number, sender)?;
-        let pool_deposit_after = querier.load_pool_deposit(&config.pool, sender)?;
-        if pool_deposit_after - prepare_status < self.deposit_delta {
-            return Ok((
-                false,
-                format!(
-                    "Delta does not satisfy condition(required: {}, delta: {})",
-                    self.deposit_delta.to_string(),
-                    pool_deposit_after.to_string(),
-                ),
+        context: &QualificationContext,
+        holder: &Addr,
+        required: &Uint256,
+    ) -> StdResult<CheckResult> {
+        let required = *required;
+        if !context.check_delta {
+            return Ok(CheckResult::skip(self.name(), required));
+        }
+
+        let pool_deposit_before =
+            match load_valid_prepare_status(context.storage, context.block, holder)? {
+                Some(prepare_status) => prepare_status.pool_deposit,
+                None => {
+                    return Ok(CheckResult::fail(
+                        self.name(),
+                        required,
+                        None::<Uint256>,
+                        FailureReason::PrepareStatusNotFound {},
+                    ))
+                }
+            };
+        let pool_deposit_after = context
+            .querier
+            .load_pool_deposits(&context.config.pools, holder)?;
+
+        // withdrawn after prepare
+        if pool_deposit_after < pool_deposit_before {
+            return Ok(CheckResult::fail(
+                self.name(),
+                required,
+                None::<Uint256>,
+                FailureReason::DepositDecreased {
+                    required,
+                    before: pool_deposit_before,
+                    after: pool_deposit_after,
+                },
+            ));
+        }
+
+        let delta = pool_deposit_after - pool_deposit_before;
+        if delta < required {
+            return Ok(CheckResult::fail(
+                self.name(),
+                required,
+                Some(delta),
+                FailureReason::DepositDeltaTooLow {
+                    required,
+                    actual: delta,
+                },
             ));
         }
 
-        Ok((true, String::default()))
+        Ok(CheckResult::pass(self.name(), required, delta))
     }
 
     fn is_satisfy_mine_stake_amount(
         &self,
-        storage: &dyn Storage,
-        querier: &Querier,
-        sender: &Addr,
-    ) -> StdResult<(bool, String)> {
-        let config = QualifierConfig::load(storage)?;
-        let stake_amount = querier.load_gov_stake_amount(&config.gov, sender)?;
-        if Uint256::from(stake_amount) < self.min_mine_stake_amount {
-            return Ok((
-                false,
-                format!(
-                    "Minimum MINE stake amount does not satisfy condition(required: {}, amount: {})",
-                    stake_amount.to_string(),
-                    self.min_mine_stake_amount.to_string(),
-                ),
+        context: &QualificationContext,
+        holder: &Addr,
+        required: &Uint256,
+        mode: &StakeMode,
+    ) -> StdResult<CheckResult> {
+        let required = *required;
+        let stake_amount = Uint256::from(context.querier.load_gov_stake_amount(
+            &context.config.gov,
+            holder,
+            mode,
+        )?);
+        if stake_amount < required {
+            return Ok(CheckResult::fail(
+                self.name(),
+                required,
+                Some(stake_amount),
+                FailureReason::MineStakeTooLow {
+                    required,
+                    actual: stake_amount,
+                },
             ));
         }
 
-        Ok((true, String::default()))
+        Ok(CheckResult::pass(self.name(), required, stake_amount))
     }
 
     fn is_satisfy_participation_count(
         &self,
-        querier: &Querier,
-        campaign: &Addr,
-        actor: &Addr,
-    ) -> StdResult<(bool, String)> {
-        let participation_count = querier.load_participation_count(campaign, actor)?;
-        if participation_count != 0 {
-            return Ok((false, "Already participated".to_string()));
+        context: &QualificationContext,
+        max: &u64,
+    ) -> StdResult<CheckResult> {
+        let participation_count = context
+            .querier
+            .load_participation_count(context.campaign, context.actor)?;
+        if participation_count >= *max {
+            return Ok(CheckResult::fail(
+                self.name(),
+                max,
+                Some(participation_count),
+                FailureReason::AlreadyParticipated {
+                    participation_count,
+                },
+            ));
         }
 
-        Ok((true, String::default()))
+        Ok(CheckResult::pass(self.name(), max, participation_count))
+    }
+
+    fn is_satisfy_participation_cooldown(
+        &self,
+        context: &QualificationContext,
+        seconds: &u64,
+    ) -> StdResult<CheckResult> {
+        let actor = context
+            .querier
+            .load_actor(context.campaign, context.actor)?;
+        if actor.participation_count == 0 {
+            return Ok(CheckResult::pass(
+                self.name(),
+                seconds,
+                "never participated",
+            ));
+        }
+
+        let eligible_at = actor.last_participated_at.plus_seconds(*seconds);
+        if context.block.time < eligible_at {
+            return Ok(CheckResult::fail(
+                self.name(),
+                seconds,
+                Some(actor.last_participated_at.seconds()),
+                FailureReason::InCooldown {
+                    last_participated_at: actor.last_participated_at,
+                    eligible_at,
+                },
+            ));
+        }
+
+        Ok(CheckResult::pass(
+            self.name(),
+            seconds,
+            actor.last_participated_at.seconds(),
+        ))
+    }
+
+    fn is_satisfy_balance(
+        &self,
+        context: &QualificationContext,
+        holder: &Addr,
+        denom: &Denom,
+        required: &Uint128,
+    ) -> StdResult<CheckResult> {
+        let required = *required;
+        let balance = context.querier.load_balance(denom, holder)?;
+        if balance < required {
+            return Ok(CheckResult::fail(
+                self.name(),
+                required,
+                Some(balance),
+                FailureReason::BalanceTooLow {
+                    denom: denom_to_string(denom),
+                    required,
+                    actual: balance,
+                },
+            ));
+        }
+
+        Ok(CheckResult::pass(self.name(), required, balance))
+    }
+
+    fn is_satisfy_balance_delta(
+        &self,
+        context: &QualificationContext,
+        holder: &Addr,
+        denom: &Denom,
+        required: &Uint128,
+    ) -> StdResult<CheckResult> {
+        let required = *required;
+        if !context.check_delta {
+            return Ok(CheckResult::skip(self.name(), required));
+        }
+
+        let prepare_status =
+            match load_valid_prepare_status(context.storage, context.block, holder)? {
+                Some(prepare_status) => prepare_status,
+                None => {
+                    return Ok(CheckResult::fail(
+                        self.name(),
+                        required,
+                        None::<Uint128>,
+                        FailureReason::PrepareStatusNotFound {},
+                    ))
+                }
+            };
+        let balance_before = match prepare_status.balance_of(denom) {
+            Some(balance) => balance,
+            None => {
+                return Ok(CheckResult::fail(
+                    self.name(),
+                    required,
+                    None::<Uint128>,
+                    FailureReason::DenomNotSnapshotted {
+                        denom: denom_to_string(denom),
+                    },
+                ))
+            }
+        };
+        let balance_after = context.querier.load_balance(denom, holder)?;
+
+        let delta = match balance_after.checked_sub(balance_before) {
+            Ok(delta) => delta,
+            Err(_) => {
+                return Ok(CheckResult::fail(
+                    self.name(),
+                    required,
+                    None::<Uint128>,
+                    FailureReason::BalanceDecreased {
+                        denom: denom_to_string(denom),
+                        required,
+                        before: balance_before,
+                        after: balance_after,
+                    },
+                ))
+            }
+        };
+        if delta < required {
+            return Ok(CheckResult::fail(
+                self.name(),
+                required,
+                Some(delta),
+                FailureReason::BalanceDeltaTooLow {
+                    denom: denom_to_string(denom),
+                    required,
+                    actual: delta,
+                },
+            ));
+        }
+
+        Ok(CheckResult::pass(self.name(), required, delta))
+    }
+
+    fn is_satisfy_mine_share(
+        &self,
+        context: &QualificationContext,
+        holder: &Addr,
+        threshold: &ShareThreshold,
+    ) -> StdResult<CheckResult> {
+        let share = context
+            .querier
+            .load_gov_staker(&context.config.gov, holder)?
+            .share;
+
+        match threshold {
+            ShareThreshold::Amount(required) => {
+                let required = *required;
+                if share < required {
+                    return Ok(CheckResult::fail(
+                        self.name(),
+                        required,
+                        Some(share),
+                        FailureReason::MineShareTooLow {
+                            required,
+                            actual: share,
+                        },
+                    ));
+                }
+
+                Ok(CheckResult::pass(self.name(), required, share))
+            }
+            ShareThreshold::Ratio(required) => {
+                let required = *required;
+                let total_share = context
+                    .querier
+                    .load_gov_state(&context.config.gov)?
+                    .total_share;
+                let ratio = if total_share.is_zero() {
+                    Decimal256::zero()
+                } else {
+                    Decimal256::from_ratio(Uint256::from(share), Uint256::from(total_share))
+                };
+                if ratio < required {
+                    return Ok(CheckResult::fail(
+                        self.name(),
+                        required,
+                        Some(ratio),
+                        FailureReason::MineShareRatioTooLow {
+                            required,
+                            actual: ratio,
+                        },
+                    ));
+                }
+
+                Ok(CheckResult::pass(self.name(), required, ratio))
+            }
+        }
+    }
+
+    // a referrer condition is not met without referrer, even inside `Or`
+    fn referrer_not_found(&self, required: impl ToString) -> CheckResult {
+        CheckResult::fail(
+            self.name(),
+            required,
+            None::<String>,
+            FailureReason::ReferrerNotFound {},
+        )
+    }
+
+    fn is_satisfy_referrer_not_self(
+        &self,
+        context: &QualificationContext,
+    ) -> StdResult<CheckResult> {
+        let referrer = match context.referrer {
+            Some(referrer) => referrer,
+            None => return Ok(self.referrer_not_found("not self")),
+        };
+        if referrer == context.actor || referrer == context.sender {
+            return Ok(CheckResult::fail(
+                self.name(),
+                "not self",
+                Some(referrer),
+                FailureReason::SelfReferral {},
+            ));
+        }
+
+        Ok(CheckResult::pass(self.name(), "not self", referrer))
+    }
+
+    fn is_satisfy_referrer_mine_stake_amount(
+        &self,
+        context: &QualificationContext,
+        required: &Uint256,
+        mode: &StakeMode,
+    ) -> StdResult<CheckResult> {
+        let required = *required;
+        let referrer = match context.referrer {
+            Some(referrer) => referrer,
+            None => return Ok(self.referrer_not_found(required)),
+        };
+        let stake_amount = Uint256::from(context.querier.load_gov_stake_amount(
+            &context.config.gov,
+            referrer,
+            mode,
+        )?);
+        if stake_amount < required {
+            return Ok(CheckResult::fail(
+                self.name(),
+                required,
+                Some(stake_amount),
+                FailureReason::ReferrerMineStakeTooLow {
+                    required,
+                    actual: stake_amount,
+                },
+            ));
+        }
+
+        Ok(CheckResult::pass(self.name(), required, stake_amount))
+    }
+
+    fn is_satisfy_referrer_participated(
+        &self,
+        context: &QualificationContext,
+    ) -> StdResult<CheckResult> {
+        let referrer = match context.referrer {
+            Some(referrer) => referrer,
+            None => return Ok(self.referrer_not_found(1)),
+        };
+        let participation_count = context
+            .querier
+            .load_participation_count(context.campaign, referrer)?;
+        if participation_count == 0 {
+            return Ok(CheckResult::fail(
+                self.name(),
+                1,
+                Some(participation_count),
+                FailureReason::ReferrerNotParticipated {},
+            ));
+        }
+
+        Ok(CheckResult::pass(self.name(), 1, participation_count))
+    }
+
+    fn is_satisfy_referral_count(
+        &self,
+        context: &QualificationContext,
+        max: &u64,
+    ) -> StdResult<CheckResult> {
+        let referrer = match context.referrer {
+            Some(referrer) => referrer,
+            None => return Ok(self.referrer_not_found(max)),
+        };
+        let referral_count = context
+            .querier
+            .load_actor(context.campaign, referrer)?
+            .referral_count;
+        if referral_count >= *max {
+            return Ok(CheckResult::fail(
+                self.name(),
+                max,
+                Some(referral_count),
+                FailureReason::ReferralCountExceeded {
+                    max: *max,
+                    actual: referral_count,
+                },
+            ));
+        }
+
+        Ok(CheckResult::pass(self.name(), max, referral_count))
+    }
+
+    fn is_satisfy_merkle_listed(&self, context: &QualificationContext) -> StdResult<CheckResult> {
+        // registration is invalidated when the root is removed
+        let root = load_merkle_registration(context.storage, context.campaign, context.actor)?
+            .filter(|root| has_merkle_root(context.storage, context.campaign, root));
+
+        match root {
+            Some(root) => Ok(CheckResult::pass(self.name(), "registered", root)),
+            None => Ok(CheckResult::fail(
+                self.name(),
+                "registered",
+                None::<String>,
+                FailureReason::NotMerkleListed {},
+            )),
+        }
+    }
+
+    fn is_satisfy_vote_count(
+        &self,
+        context: &QualificationContext,
+        holder: &Addr,
+        required: &u64,
+    ) -> StdResult<CheckResult> {
+        let required = *required;
+        let staker = context
+            .querier
+            .load_gov_staker(&context.config.gov, holder)?;
+        let vote_count = staker.locked_balance.len() as u64;
+        if vote_count < required {
+            return Ok(CheckResult::fail(
+                self.name(),
+                required,
+                Some(vote_count),
+                FailureReason::VoteCountTooLow {
+                    required,
+                    actual: vote_count,
+                },
+            ));
+        }
+
+        Ok(CheckResult::pass(self.name(), required, vote_count))
+    }
+
+    fn is_satisfy_voted(
+        &self,
+        context: &QualificationContext,
+        holder: &Addr,
+        poll_id: &u64,
+        vote: &Option<VoteOption>,
+    ) -> StdResult<CheckResult> {
+        let required = match vote {
+            Some(vote) => format!("{}:{}", poll_id, vote),
+            None => poll_id.to_string(),
+        };
+        let staker = context
+            .querier
+            .load_gov_staker(&context.config.gov, holder)?;
+        let actual_vote = staker
+            .locked_balance
+            .into_iter()
+            .find(|(id, _)| id == poll_id)
+            .map(|(_, voter_info)| voter_info.vote);
+
+        match (vote, &actual_vote) {
+            (None, Some(actual)) => Ok(CheckResult::pass(self.name(), required, actual)),
+            (Some(vote), Some(actual)) if vote == actual => {
+                Ok(CheckResult::pass(self.name(), required, actual))
+            }
+            _ => Ok(CheckResult::fail(
+                self.name(),
+                required,
+                actual_vote.clone(),
+                FailureReason::NotVoted {
+                    poll_id: *poll_id,
+                    required_vote: vote.clone(),
+                    actual_vote,
+                },
+            )),
+        }
+    }
+}
+
+/// Evaluates the check on every holder, returning the first failure
+fn evaluate_on_holders<'a, F>(
+    context: &QualificationContext<'a>,
+    check: F,
+) -> StdResult<CheckResult>
+where
+    F: Fn(&'a Addr) -> StdResult<CheckResult>,
+{
+    let holders = context.holders();
+    let mut result = check(holders[0])?;
+    for holder in holders[1..].iter() {
+        if !result.satisfied {
+            break;
+        }
+        result = check(holder)?;
+    }
+
+    Ok(result)
+}
+
+pub fn validate_denom(api: &dyn Api, denom: &Denom) -> Result<(), ContractError> {
+    match denom {
+        Denom::Native(denom) if denom.is_empty() => Err(ContractError::InvalidArgument {
+            msg: "Empty native denom".to_string(),
+        }),
+        Denom::Native(_) => Ok(()),
+        Denom::Cw20(token_contract) => {
+            api.addr_validate(token_contract.as_str())?;
+            Ok(())
+        }
     }
 }
 
@@ -206,7 +1717,6 @@ impl Querier<'_> {
         Querier { querier }
     }
 
-    #[allow(dead_code)]
     pub fn load_balance(&self, denom: &Denom, address: &Addr) -> StdResult<Uint128> {
         match denom {
             Denom::Native(denom) => self.load_native_balance(denom, address),
@@ -240,31 +1750,64 @@ impl Querier<'_> {
         Ok(balance.amount)
     }
 
-    pub fn load_gov_stake_amount(&self, gov: &Addr, staker: &Addr) -> StdResult<Uint128> {
-        let staker: GovStakerResponse = self.querier.query_wasm_smart(
+    /// Sums up the deposits of every pool, applying the weight of each pool.
+    pub fn load_pool_deposits(&self, pools: &[PoolConfig], staker: &Addr) -> StdResult<Uint256> {
+        let mut total = Uint256::zero();
+        for pool in pools.iter() {
+            total += self.load_pool_deposit(&pool.address, staker)? * pool.weight;
+        }
+
+        Ok(total)
+    }
+
+    pub fn load_balances(
+        &self,
+        denoms: &[Denom],
+        address: &Addr,
+    ) -> StdResult<Vec<(Denom, Uint128)>> {
+        denoms
+            .iter()
+            .map(|denom| Ok((denom.clone(), self.load_balance(denom, address)?)))
+            .collect()
+    }
+
+    pub fn load_gov_state(&self, gov: &Addr) -> StdResult<GovStateResponse> {
+        self.querier.query_wasm_smart(gov, &GovQueryMsg::State {})
+    }
+
+    pub fn load_gov_staker(&self, gov: &Addr, staker: &Addr) -> StdResult<GovStakerResponse> {
+        self.querier.query_wasm_smart(
             gov,
             &GovQueryMsg::Staker {
                 address: staker.to_string(),
             },
-        )?;
+        )
+    }
 
-        Ok(staker.balance)
+    pub fn load_gov_stake_amount(
+        &self,
+        gov: &Addr,
+        staker: &Addr,
+        mode: &StakeMode,
+    ) -> StdResult<Uint128> {
+        Ok(mode.stake_amount(&self.load_gov_staker(gov, staker)?))
     }
 
-    pub fn load_participation_count(&self, campaign: &Addr, address: &Addr) -> StdResult<u64> {
-        let actor: ActorResponse = self.querier.query_wasm_smart(
+    pub fn load_actor(&self, campaign: &Addr, address: &Addr) -> StdResult<ActorResponse> {
+        self.querier.query_wasm_smart(
             campaign,
             &valkyrie::campaign::query_msgs::QueryMsg::Actor {
                 address: address.to_string(),
             },
-        )?;
+        )
+    }
 
-        Ok(actor.participation_count)
+    pub fn load_participation_count(&self, campaign: &Addr, address: &Addr) -> StdResult<u64> {
+        Ok(self.load_actor(campaign, address)?.participation_count)
     }
 }
 
-#[allow(dead_code)]
-fn denom_to_string(denom: &Denom) -> String {
+pub fn denom_to_string(denom: &Denom) -> String {
     match denom {
         Denom::Native(denom) => denom.to_string(),
         Denom::Cw20(address) => address.to_string(),
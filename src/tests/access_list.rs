@@ -0,0 +1,271 @@
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::testing::mock_info;
+use cosmwasm_std::{Env, MessageInfo, Response};
+use valkyrie_qualifier::QualifiedContinueOption;
+
+use crate::errors::ContractError;
+use crate::executions::{add_to_access_list, remove_from_access_list, ExecuteResult};
+use crate::msgs::{AccessListResponse, ConfigureMsg, FailureReason};
+use crate::queries;
+use crate::states::{AccessList, AllowlistPolicy, Condition, Requirement, StakeMode};
+use crate::tests::qualify::exec_default;
+use crate::tests::{
+    exec_configure, mock_deps, mock_gov_stake, mock_participation_count, qualifier_creator_sender,
+    qualifier_env, setup_requirement, MockDeps, CAMPAIGN, TESTER,
+};
+
+const STAKE_AMOUNT: u64 = 1000000u64;
+
+pub fn exec_add(
+    deps: &mut MockDeps,
+    env: Env,
+    info: MessageInfo,
+    list: AccessList,
+    addresses: &[&str],
+) -> ExecuteResult {
+    add_to_access_list(
+        deps.as_mut(),
+        env,
+        info,
+        list,
+        addresses.iter().map(|a| a.to_string()).collect(),
+    )
+}
+
+pub fn exec_remove(
+    deps: &mut MockDeps,
+    env: Env,
+    info: MessageInfo,
+    list: AccessList,
+    addresses: &[&str],
+) -> ExecuteResult {
+    remove_from_access_list(
+        deps.as_mut(),
+        env,
+        info,
+        list,
+        addresses.iter().map(|a| a.to_string()).collect(),
+    )
+}
+
+fn setup(deps: &mut MockDeps, allowlist_policy: AllowlistPolicy, participation_count: u64) {
+    setup_requirement(
+        deps,
+        Requirement::And(vec![
+            Requirement::Condition(Condition::MineStake {
+                min: Uint256::from(STAKE_AMOUNT),
+                mode: StakeMode::Total,
+            }),
+            Requirement::Condition(Condition::ParticipationCount { max: 1 }),
+        ]),
+    );
+    mock_gov_stake(deps, 0);
+    mock_participation_count(deps, participation_count);
+    exec_configure(
+        deps,
+        ConfigureMsg {
+            allowlist_policy: Some(allowlist_policy),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn manage_lists() {
+    let mut deps = mock_deps();
+    setup(&mut deps, AllowlistPolicy::Eligible, 0);
+    let env = qualifier_env();
+    let info = qualifier_creator_sender();
+
+    let response = exec_add(
+        &mut deps,
+        env.clone(),
+        info.clone(),
+        AccessList::Allowlist,
+        &[TESTER, CAMPAIGN],
+    )
+    .unwrap();
+    assert_eq!(
+        response,
+        Response::default()
+            .add_attribute("action", "add_to_access_list")
+            .add_attribute("list", "allowlist")
+            .add_attribute("count", "2")
+    );
+
+    let response = queries::access_list(
+        deps.as_ref(),
+        env.clone(),
+        AccessList::Allowlist,
+        None,
+        Some(1),
+    )
+    .unwrap();
+    assert_eq!(
+        response,
+        AccessListResponse {
+            list: AccessList::Allowlist,
+            addresses: vec![CAMPAIGN.to_string()],
+        }
+    );
+    let response = queries::access_list(
+        deps.as_ref(),
+        env.clone(),
+        AccessList::Allowlist,
+        Some(CAMPAIGN.to_string()),
+        None,
+    )
+    .unwrap();
+    assert_eq!(response.addresses, vec![TESTER.to_string()]);
+
+    exec_remove(
+        &mut deps,
+        env.clone(),
+        info,
+        AccessList::Allowlist,
+        &[CAMPAIGN],
+    )
+    .unwrap();
+    let response = queries::access_list(
+        deps.as_ref(),
+        env.clone(),
+        AccessList::Allowlist,
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(response.addresses, vec![TESTER.to_string()]);
+    let response =
+        queries::access_list(deps.as_ref(), env.clone(), AccessList::Denylist, None, None).unwrap();
+    assert!(response.addresses.is_empty());
+
+    let result = exec_add(
+        &mut deps,
+        env,
+        mock_info(TESTER, &[]),
+        AccessList::Denylist,
+        &[TESTER],
+    );
+    assert!(matches!(result, Err(ContractError::Unauthorized {})));
+}
+
+#[test]
+fn denylisted() {
+    let mut deps = mock_deps();
+    setup(&mut deps, AllowlistPolicy::Eligible, 0);
+    let env = qualifier_env();
+    let info = qualifier_creator_sender();
+
+    // denylist comes first
+    exec_add(
+        &mut deps,
+        env.clone(),
+        info.clone(),
+        AccessList::Allowlist,
+        &[TESTER],
+    )
+    .unwrap();
+    exec_add(
+        &mut deps,
+        env.clone(),
+        info,
+        AccessList::Denylist,
+        &[TESTER],
+    )
+    .unwrap();
+
+    let result = exec_default(&mut deps, env);
+    assert_eq!(result.continue_option, QualifiedContinueOption::Ineligible);
+    assert_eq!(
+        result.reason,
+        Some(
+            FailureReason::Denylisted {
+                address: TESTER.to_string()
+            }
+            .to_json_string()
+            .unwrap()
+        )
+    );
+}
+
+#[test]
+fn allowlisted() {
+    // eligible regardless of the requirement
+    let mut deps = mock_deps();
+    setup(&mut deps, AllowlistPolicy::Eligible, 1);
+    let env = qualifier_env();
+    exec_add(
+        &mut deps,
+        env.clone(),
+        qualifier_creator_sender(),
+        AccessList::Allowlist,
+        &[TESTER],
+    )
+    .unwrap();
+
+    let result = exec_default(&mut deps, env);
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+
+    // only the stake condition is skipped
+    let mut deps = mock_deps();
+    setup(&mut deps, AllowlistPolicy::SkipStakeAndDeposit, 0);
+    let env = qualifier_env();
+    exec_add(
+        &mut deps,
+        env.clone(),
+        qualifier_creator_sender(),
+        AccessList::Allowlist,
+        &[TESTER],
+    )
+    .unwrap();
+
+    let result = exec_default(&mut deps, env.clone());
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+
+    mock_participation_count(&mut deps, 1);
+    let result = exec_default(&mut deps, env);
+    assert_eq!(
+        result.reason,
+        Some(
+            FailureReason::AlreadyParticipated {
+                participation_count: 1
+            }
+            .to_json_string()
+            .unwrap()
+        )
+    );
+}
+
+#[test]
+fn allowlisted_by_default() {
+    let mut deps = mock_deps();
+    setup_requirement(
+        &mut deps,
+        Requirement::Condition(Condition::ParticipationCount { max: 1 }),
+    );
+    mock_participation_count(&mut deps, 1);
+    let env = qualifier_env();
+    exec_add(
+        &mut deps,
+        env.clone(),
+        qualifier_creator_sender(),
+        AccessList::Allowlist,
+        &[TESTER],
+    )
+    .unwrap();
+
+    // allowlisted actors can not participate again
+    let result = exec_default(&mut deps, env);
+    assert_eq!(result.continue_option, QualifiedContinueOption::Ineligible);
+    assert_eq!(
+        result.reason,
+        Some(
+            FailureReason::AlreadyParticipated {
+                participation_count: 1
+            }
+            .to_json_string()
+            .unwrap()
+        )
+    );
+}
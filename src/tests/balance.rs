@@ -0,0 +1,95 @@
+use cosmwasm_std::{coin, from_binary, to_binary, Addr, Uint128};
+use cw20::{BalanceResponse, Cw20QueryMsg, Denom};
+use valkyrie_qualifier::QualifiedContinueOption;
+
+use crate::msgs::FailureReason;
+use crate::states::{Condition, Holder, Requirement};
+use crate::tests::qualify::exec_default;
+use crate::tests::{mock_deps, qualifier_env, setup_requirement, MockDeps, TESTER};
+
+const MINE_TOKEN: &str = "terra1kcthelkax4j9x8d3ny6sdag0qmxxynl3qtcrpy";
+const BALANCE_AMOUNT: u128 = 1000000u128;
+
+fn mock_cw20_balance(deps: &mut MockDeps, balance: u128) {
+    deps.querier.register_wasm_smart_query_handler(
+        MINE_TOKEN.to_string(),
+        Box::new(move |x| match from_binary::<Cw20QueryMsg>(x).unwrap() {
+            Cw20QueryMsg::Balance { .. } => to_binary(&BalanceResponse {
+                balance: Uint128::from(balance),
+            }),
+            _ => panic!("unexpected query"),
+        }),
+    );
+}
+
+fn balance_requirement() -> Requirement {
+    Requirement::And(vec![
+        Requirement::Condition(Condition::Balance {
+            denom: Denom::Native("uusd".to_string()),
+            min: Uint128::from(BALANCE_AMOUNT),
+            holder: Holder::Sender,
+        }),
+        Requirement::Condition(Condition::Balance {
+            denom: Denom::Cw20(Addr::unchecked(MINE_TOKEN)),
+            min: Uint128::from(BALANCE_AMOUNT),
+            holder: Holder::Actor,
+        }),
+    ])
+}
+
+fn setup(deps: &mut MockDeps, native_balance: u128, cw20_balance: u128) {
+    mock_cw20_balance(deps, cw20_balance);
+    deps.querier
+        .update_balance(TESTER.to_string(), vec![coin(native_balance, "uusd")]);
+
+    setup_requirement(deps, balance_requirement());
+}
+
+#[test]
+fn succeed() {
+    let mut deps = mock_deps();
+    setup(&mut deps, BALANCE_AMOUNT, BALANCE_AMOUNT);
+
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+}
+
+#[test]
+fn failed_with_native_balance() {
+    let mut deps = mock_deps();
+    setup(&mut deps, BALANCE_AMOUNT - 1, BALANCE_AMOUNT);
+
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(
+        result.reason,
+        Some(
+            FailureReason::BalanceTooLow {
+                denom: "uusd".to_string(),
+                required: Uint128::from(BALANCE_AMOUNT),
+                actual: Uint128::from(BALANCE_AMOUNT - 1),
+            }
+            .to_json_string()
+            .unwrap()
+        )
+    );
+}
+
+#[test]
+fn failed_with_cw20_balance() {
+    let mut deps = mock_deps();
+    setup(&mut deps, BALANCE_AMOUNT, 0);
+
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(
+        result.reason,
+        Some(
+            FailureReason::BalanceTooLow {
+                denom: MINE_TOKEN.to_string(),
+                required: Uint128::from(BALANCE_AMOUNT),
+                actual: Uint128::zero(),
+            }
+            .to_json_string()
+            .unwrap()
+        )
+    );
+}
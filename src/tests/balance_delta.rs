@@ -0,0 +1,172 @@
+use cosmwasm_std::testing::mock_info;
+use cosmwasm_std::{coin, Addr, Uint128};
+use cw20::Denom;
+use valkyrie_qualifier::QualifiedContinueOption;
+
+use crate::errors::ContractError;
+use crate::executions::{set_campaign_profile, update_requirement};
+use crate::msgs::{ConfigureMsg, FailureReason};
+use crate::states::{load_latest_prepare_status, Condition, Requirement};
+use crate::tests::qualify::{exec_default, instantiate_with_requirement};
+use crate::tests::{
+    exec_configure, mock_deps, mock_participation_count, mock_pool_deposit,
+    qualifier_creator_sender, qualifier_env, MockDeps, CAMPAIGN, TESTER,
+};
+
+const DELTA_AMOUNT: u128 = 1000000u128;
+
+fn configure_snapshot_denoms(
+    deps: &mut MockDeps,
+    snapshot_denoms: Vec<Denom>,
+) -> Result<(), ContractError> {
+    exec_configure(
+        deps,
+        ConfigureMsg {
+            snapshot_denoms: Some(snapshot_denoms),
+            ..Default::default()
+        },
+    )
+    .map(|_| ())
+}
+
+fn balance_delta_requirement() -> Requirement {
+    Requirement::Condition(Condition::BalanceDelta {
+        denom: Denom::Native("uusd".to_string()),
+        min: Uint128::from(DELTA_AMOUNT),
+    })
+}
+
+fn setup(deps: &mut MockDeps, balance_before: u128) {
+    mock_participation_count(deps, 0);
+    mock_pool_deposit(deps, 0);
+    deps.querier
+        .update_balance(TESTER.to_string(), vec![coin(balance_before, "uusd")]);
+
+    instantiate_with_requirement(
+        deps,
+        Requirement::Condition(Condition::ParticipationCount { max: 1 }),
+        None,
+    );
+    configure_snapshot_denoms(deps, vec![Denom::Native("uusd".to_string())]).unwrap();
+    update_requirement(
+        deps.as_mut(),
+        qualifier_env(),
+        qualifier_creator_sender(),
+        balance_delta_requirement(),
+    )
+    .unwrap();
+
+    super::prepare::exec(deps, qualifier_env(), mock_info(TESTER, &[])).unwrap();
+}
+
+#[test]
+fn succeed() {
+    let mut deps = mock_deps();
+    setup(&mut deps, 100);
+
+    let prepare_status = load_latest_prepare_status(&deps.storage, &Addr::unchecked(TESTER))
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        prepare_status.balances,
+        vec![(Denom::Native("uusd".to_string()), Uint128::from(100u128))]
+    );
+
+    deps.querier
+        .update_balance(TESTER.to_string(), vec![coin(100 + DELTA_AMOUNT, "uusd")]);
+
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+}
+
+#[test]
+fn failed() {
+    let mut deps = mock_deps();
+    setup(&mut deps, 100);
+
+    deps.querier.update_balance(
+        TESTER.to_string(),
+        vec![coin(100 + DELTA_AMOUNT - 1, "uusd")],
+    );
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(
+        result.reason,
+        Some(
+            FailureReason::BalanceDeltaTooLow {
+                denom: "uusd".to_string(),
+                required: Uint128::from(DELTA_AMOUNT),
+                actual: Uint128::from(DELTA_AMOUNT - 1),
+            }
+            .to_json_string()
+            .unwrap()
+        )
+    );
+
+    super::prepare::exec(&mut deps, qualifier_env(), mock_info(TESTER, &[])).unwrap();
+    deps.querier
+        .update_balance(TESTER.to_string(), vec![coin(99, "uusd")]);
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(
+        result.reason,
+        Some(
+            FailureReason::BalanceDecreased {
+                denom: "uusd".to_string(),
+                required: Uint128::from(DELTA_AMOUNT),
+                before: Uint128::from(100 + DELTA_AMOUNT - 1),
+                after: Uint128::from(99u128),
+            }
+            .to_json_string()
+            .unwrap()
+        )
+    );
+}
+
+#[test]
+fn reject_denom_not_snapshotted() {
+    let mut deps = mock_deps();
+    setup(&mut deps, 100);
+
+    // the requirement in use refers to the denom
+    let result = configure_snapshot_denoms(&mut deps, vec![]);
+    assert!(matches!(result, Err(ContractError::InvalidArgument { .. })));
+
+    let result = set_campaign_profile(
+        deps.as_mut(),
+        qualifier_env(),
+        qualifier_creator_sender(),
+        CAMPAIGN.to_string(),
+        Requirement::Condition(Condition::BalanceDelta {
+            denom: Denom::Native("uluna".to_string()),
+            min: Uint128::from(DELTA_AMOUNT),
+        }),
+        QualifiedContinueOption::Ineligible,
+        None,
+    );
+    assert!(matches!(result, Err(ContractError::InvalidArgument { .. })));
+
+    update_requirement(
+        deps.as_mut(),
+        qualifier_env(),
+        qualifier_creator_sender(),
+        Requirement::Condition(Condition::ParticipationCount { max: 1 }),
+    )
+    .unwrap();
+    configure_snapshot_denoms(&mut deps, vec![]).unwrap();
+
+    let result = update_requirement(
+        deps.as_mut(),
+        qualifier_env(),
+        qualifier_creator_sender(),
+        balance_delta_requirement(),
+    );
+    assert!(matches!(result, Err(ContractError::InvalidArgument { .. })));
+
+    let result = configure_snapshot_denoms(
+        &mut deps,
+        vec![
+            Denom::Native("uusd".to_string()),
+            Denom::Native("uusd".to_string()),
+        ],
+    );
+    assert!(matches!(result, Err(ContractError::InvalidArgument { .. })));
+}
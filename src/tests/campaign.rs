@@ -0,0 +1,79 @@
+use cosmwasm_std::testing::mock_info;
+use cosmwasm_std::{Addr, Env, MessageInfo, Response};
+
+use crate::errors::ContractError;
+use crate::executions::{add_campaign, remove_campaign, ExecuteResult};
+use crate::msgs::CampaignsResponse;
+use crate::queries;
+use crate::states::is_registered_campaign;
+use crate::tests::{mock_deps, MockDeps, CAMPAIGN, TESTER};
+
+pub fn exec_add(deps: &mut MockDeps, env: Env, info: MessageInfo, campaign: &str) -> ExecuteResult {
+    add_campaign(deps.as_mut(), env, info, campaign.to_string())
+}
+
+pub fn exec_remove(
+    deps: &mut MockDeps,
+    env: Env,
+    info: MessageInfo,
+    campaign: &str,
+) -> ExecuteResult {
+    remove_campaign(deps.as_mut(), env, info, campaign.to_string())
+}
+
+#[test]
+fn succeed() {
+    let mut deps = mock_deps();
+
+    let (env, info, _) = super::instantiate::default(&mut deps);
+
+    let response = exec_add(&mut deps, env.clone(), info.clone(), CAMPAIGN).unwrap();
+    assert_eq!(
+        response,
+        Response::default()
+            .add_attribute("action", "add_campaign")
+            .add_attribute("campaign", CAMPAIGN)
+    );
+    exec_add(&mut deps, env.clone(), info.clone(), TESTER).unwrap();
+
+    assert!(is_registered_campaign(
+        deps.as_ref().storage,
+        &Addr::unchecked(CAMPAIGN)
+    ));
+
+    let response = queries::campaigns(deps.as_ref(), env.clone(), None, None).unwrap();
+    assert_eq!(
+        response,
+        CampaignsResponse {
+            campaigns: vec![CAMPAIGN.to_string(), TESTER.to_string()],
+        }
+    );
+
+    let response =
+        queries::campaigns(deps.as_ref(), env.clone(), Some(CAMPAIGN.to_string()), None).unwrap();
+    assert_eq!(response.campaigns, vec![TESTER.to_string()]);
+
+    let response = exec_remove(&mut deps, env.clone(), info, CAMPAIGN).unwrap();
+    assert_eq!(
+        response,
+        Response::default()
+            .add_attribute("action", "remove_campaign")
+            .add_attribute("campaign", CAMPAIGN)
+    );
+
+    let response = queries::campaigns(deps.as_ref(), env, None, None).unwrap();
+    assert_eq!(response.campaigns, vec![TESTER.to_string()]);
+}
+
+#[test]
+fn failed_without_permission() {
+    let mut deps = mock_deps();
+
+    let (env, _, _) = super::instantiate::default(&mut deps);
+
+    let result = exec_add(&mut deps, env.clone(), mock_info(TESTER, &[]), CAMPAIGN);
+    assert_eq!(result, Err(ContractError::Unauthorized {}));
+
+    let result = exec_remove(&mut deps, env, mock_info(TESTER, &[]), CAMPAIGN);
+    assert_eq!(result, Err(ContractError::Unauthorized {}));
+}
@@ -0,0 +1,158 @@
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::testing::mock_info;
+use cosmwasm_std::{Env, MessageInfo, Response};
+use valkyrie_qualifier::QualifiedContinueOption;
+
+use crate::errors::ContractError;
+use crate::executions::{remove_campaign_profile, set_campaign_profile, ExecuteResult};
+use crate::msgs::{to_json_string, CampaignProfileResponse, FailureReason};
+use crate::queries;
+use crate::states::{CampaignProfile, Condition, Requirement, StakeMode};
+use crate::tests::qualify::{exec_default, instantiate_with};
+use crate::tests::{
+    default_requirement, mock_deps, mock_gov_stake, mock_participation_count, mock_pool_deposit,
+    qualifier_creator_sender, qualifier_env, MockDeps, CAMPAIGN, TESTER,
+};
+
+const DEPOSIT_AMOUNT: u64 = 1000000u64;
+
+pub fn exec_set(
+    deps: &mut MockDeps,
+    env: Env,
+    info: MessageInfo,
+    campaign: &str,
+    requirement: Requirement,
+    continue_option_on_fail: QualifiedContinueOption,
+) -> ExecuteResult {
+    set_campaign_profile(
+        deps.as_mut(),
+        env,
+        info,
+        campaign.to_string(),
+        requirement,
+        continue_option_on_fail,
+        None,
+    )
+}
+
+pub fn exec_remove(
+    deps: &mut MockDeps,
+    env: Env,
+    info: MessageInfo,
+    campaign: &str,
+) -> ExecuteResult {
+    remove_campaign_profile(deps.as_mut(), env, info, campaign.to_string())
+}
+
+fn stake_requirement() -> Requirement {
+    Requirement::Condition(Condition::MineStake {
+        min: Uint256::from(DEPOSIT_AMOUNT),
+        mode: StakeMode::Total,
+    })
+}
+
+#[test]
+fn succeed() {
+    let mut deps = mock_deps();
+    mock_gov_stake(&mut deps, 0);
+    mock_participation_count(&mut deps, 0);
+    mock_pool_deposit(&mut deps, 0);
+
+    instantiate_with(&mut deps, 0, 0, None);
+
+    let env = qualifier_env();
+    let response = exec_set(
+        &mut deps,
+        env.clone(),
+        qualifier_creator_sender(),
+        CAMPAIGN,
+        stake_requirement(),
+        QualifiedContinueOption::Eligible,
+    )
+    .unwrap();
+    assert_eq!(
+        response,
+        Response::default()
+            .add_attribute("action", "set_campaign_profile")
+            .add_attribute("campaign", CAMPAIGN)
+            .add_attribute("requirement", to_json_string(&stake_requirement()).unwrap())
+            .add_attribute("continue_option_on_fail", "eligible")
+    );
+
+    let response =
+        queries::campaign_profile(deps.as_ref(), env.clone(), CAMPAIGN.to_string()).unwrap();
+    assert_eq!(
+        response,
+        CampaignProfileResponse {
+            campaign: CAMPAIGN.to_string(),
+            is_default: false,
+            profile: CampaignProfile {
+                requirement: stake_requirement(),
+                continue_option_on_fail: QualifiedContinueOption::Eligible,
+                continue_option_on_referrer_fail: None,
+            },
+        }
+    );
+
+    let response = queries::campaign_profiles(deps.as_ref(), env.clone(), None, None).unwrap();
+    assert_eq!(response.profiles.len(), 1);
+
+    // evaluated with the campaign profile
+    super::prepare::exec(&mut deps, env.clone(), mock_info(TESTER, &[])).unwrap();
+    let result = exec_default(&mut deps, env.clone());
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+    assert_eq!(
+        result.reason,
+        Some(
+            FailureReason::MineStakeTooLow {
+                required: Uint256::from(DEPOSIT_AMOUNT),
+                actual: Uint256::zero(),
+            }
+            .to_json_string()
+            .unwrap()
+        )
+    );
+
+    exec_remove(&mut deps, env.clone(), qualifier_creator_sender(), CAMPAIGN).unwrap();
+
+    // falls back to the default profile
+    let response =
+        queries::campaign_profile(deps.as_ref(), env.clone(), CAMPAIGN.to_string()).unwrap();
+    assert_eq!(
+        response,
+        CampaignProfileResponse {
+            campaign: CAMPAIGN.to_string(),
+            is_default: true,
+            profile: CampaignProfile {
+                requirement: default_requirement(0, 0),
+                continue_option_on_fail: QualifiedContinueOption::Ineligible,
+                continue_option_on_referrer_fail: None,
+            },
+        }
+    );
+
+    super::prepare::exec(&mut deps, env.clone(), mock_info(TESTER, &[])).unwrap();
+    let result = exec_default(&mut deps, env);
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+    assert_eq!(result.reason, None);
+}
+
+#[test]
+fn failed_without_permission() {
+    let mut deps = mock_deps();
+
+    let (env, _, _) = super::instantiate::default(&mut deps);
+
+    let result = exec_set(
+        &mut deps,
+        env.clone(),
+        mock_info(TESTER, &[]),
+        CAMPAIGN,
+        stake_requirement(),
+        QualifiedContinueOption::Eligible,
+    );
+    assert_eq!(result, Err(ContractError::Unauthorized {}));
+
+    let result = exec_remove(&mut deps, env, mock_info(TESTER, &[]), CAMPAIGN);
+    assert_eq!(result, Err(ContractError::Unauthorized {}));
+}
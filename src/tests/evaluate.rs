@@ -0,0 +1,156 @@
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::testing::mock_info;
+use valkyrie_qualifier::{QualificationMsg, QualifiedContinueOption};
+
+use crate::msgs::{CheckResult, EvaluationResponse, FailureReason};
+use crate::queries;
+use crate::states::{Condition, Requirement, StakeMode};
+use crate::tests::qualify::{instantiate_with, instantiate_with_requirement};
+use crate::tests::{
+    mock_deps, mock_gov_stake, mock_participation_count, mock_pool_deposit, qualifier_env,
+    CAMPAIGN, TESTER,
+};
+
+const DEPOSIT_AMOUNT: u64 = 1000000u64;
+
+#[test]
+fn report_every_check() {
+    let mut deps = mock_deps();
+    mock_gov_stake(&mut deps, DEPOSIT_AMOUNT);
+    mock_participation_count(&mut deps, 1);
+    mock_pool_deposit(&mut deps, 0);
+
+    instantiate_with(&mut deps, DEPOSIT_AMOUNT, DEPOSIT_AMOUNT * 2, None);
+
+    let env = qualifier_env();
+    super::prepare::exec(&mut deps, env.clone(), mock_info(TESTER, &[])).unwrap();
+
+    mock_pool_deposit(&mut deps, DEPOSIT_AMOUNT / 2);
+
+    let response = queries::evaluate(
+        deps.as_ref(),
+        env,
+        QualificationMsg {
+            campaign: CAMPAIGN.to_string(),
+            sender: TESTER.to_string(),
+            actor: TESTER.to_string(),
+            referrer: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        response,
+        EvaluationResponse {
+            continue_option: QualifiedContinueOption::Ineligible,
+            checks: vec![
+                CheckResult::fail(
+                    "deposit_delta",
+                    DEPOSIT_AMOUNT,
+                    Some(DEPOSIT_AMOUNT / 2),
+                    FailureReason::DepositDeltaTooLow {
+                        required: Uint256::from(DEPOSIT_AMOUNT),
+                        actual: Uint256::from(DEPOSIT_AMOUNT / 2),
+                    }
+                ),
+                CheckResult::fail(
+                    "mine_stake",
+                    DEPOSIT_AMOUNT * 2,
+                    Some(DEPOSIT_AMOUNT),
+                    FailureReason::MineStakeTooLow {
+                        required: Uint256::from(DEPOSIT_AMOUNT * 2),
+                        actual: Uint256::from(DEPOSIT_AMOUNT),
+                    }
+                ),
+                CheckResult::fail(
+                    "participation_count",
+                    1u64,
+                    Some(1u64),
+                    FailureReason::AlreadyParticipated {
+                        participation_count: 1
+                    }
+                ),
+            ],
+        }
+    );
+}
+
+#[test]
+fn report_passed_checks() {
+    let mut deps = mock_deps();
+    mock_gov_stake(&mut deps, DEPOSIT_AMOUNT);
+    mock_participation_count(&mut deps, 0);
+    mock_pool_deposit(&mut deps, 0);
+
+    instantiate_with(&mut deps, DEPOSIT_AMOUNT, DEPOSIT_AMOUNT, None);
+
+    let env = qualifier_env();
+    super::prepare::exec(&mut deps, env.clone(), mock_info(TESTER, &[])).unwrap();
+
+    mock_pool_deposit(&mut deps, DEPOSIT_AMOUNT);
+
+    let response = queries::evaluate(
+        deps.as_ref(),
+        env,
+        QualificationMsg {
+            campaign: CAMPAIGN.to_string(),
+            sender: TESTER.to_string(),
+            actor: TESTER.to_string(),
+            referrer: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(response.continue_option, QualifiedContinueOption::Eligible);
+    assert!(response.checks.iter().all(|c| c.satisfied));
+    assert_eq!(
+        response.checks[1],
+        CheckResult::pass("mine_stake", DEPOSIT_AMOUNT, DEPOSIT_AMOUNT)
+    );
+}
+
+#[test]
+fn report_negated_check() {
+    let mut deps = mock_deps();
+    mock_gov_stake(&mut deps, DEPOSIT_AMOUNT);
+    mock_participation_count(&mut deps, 0);
+    mock_pool_deposit(&mut deps, 0);
+
+    let not_mine_stake = Requirement::Not(Box::new(Requirement::Condition(Condition::MineStake {
+        min: Uint256::from(DEPOSIT_AMOUNT),
+        mode: StakeMode::Total,
+    })));
+    instantiate_with_requirement(&mut deps, not_mine_stake, None);
+
+    let msg = QualificationMsg {
+        campaign: CAMPAIGN.to_string(),
+        sender: TESTER.to_string(),
+        actor: TESTER.to_string(),
+        referrer: None,
+    };
+    let response = queries::evaluate(deps.as_ref(), qualifier_env(), msg.clone()).unwrap();
+    assert_eq!(
+        response,
+        EvaluationResponse {
+            continue_option: QualifiedContinueOption::Ineligible,
+            checks: vec![
+                CheckResult::pass("mine_stake", DEPOSIT_AMOUNT, DEPOSIT_AMOUNT),
+                CheckResult::fail(
+                    "not",
+                    "not mine_stake",
+                    Some("satisfied"),
+                    FailureReason::NegatedRequirementSatisfied {
+                        requirement: "mine_stake".to_string(),
+                    }
+                ),
+            ],
+        }
+    );
+
+    // the failing check of the child is overruled by the node
+    mock_gov_stake(&mut deps, 0);
+    let response = queries::evaluate(deps.as_ref(), qualifier_env(), msg).unwrap();
+    assert_eq!(response.continue_option, QualifiedContinueOption::Eligible);
+    assert_eq!(
+        response.checks.last(),
+        Some(&CheckResult::pass("not", "not mine_stake", "not satisfied"))
+    );
+}
@@ -0,0 +1,116 @@
+use cosmwasm_std::Uint128;
+use valkyrie_qualifier::QualifiedContinueOption;
+
+use crate::msgs::{FailureReason, GovStakerResponse, VoteOption, VoterInfo};
+use crate::states::{Condition, Requirement};
+use crate::tests::qualify::exec_default;
+use crate::tests::{mock_deps, mock_gov_staker, qualifier_env, setup_requirement, MockDeps};
+
+fn setup(deps: &mut MockDeps, condition: Condition) {
+    mock_gov_staker(
+        deps,
+        GovStakerResponse {
+            balance: Uint128::from(1000u64),
+            share: Default::default(),
+            locked_balance: vec![
+                (
+                    1,
+                    VoterInfo {
+                        vote: VoteOption::Yes,
+                        balance: Uint128::from(1000u64),
+                    },
+                ),
+                (
+                    3,
+                    VoterInfo {
+                        vote: VoteOption::No,
+                        balance: Uint128::from(500u64),
+                    },
+                ),
+            ],
+        },
+    );
+
+    setup_requirement(deps, Requirement::Condition(condition));
+}
+
+#[test]
+fn vote_count() {
+    let mut deps = mock_deps();
+    setup(&mut deps, Condition::VoteCount { min: 2 });
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+
+    let mut deps = mock_deps();
+    setup(&mut deps, Condition::VoteCount { min: 3 });
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(
+        result.reason,
+        Some(
+            FailureReason::VoteCountTooLow {
+                required: 3,
+                actual: 2,
+            }
+            .to_json_string()
+            .unwrap()
+        )
+    );
+}
+
+#[test]
+fn voted() {
+    for (poll_id, vote) in [
+        (1, None),
+        (1, Some(VoteOption::Yes)),
+        (3, Some(VoteOption::No)),
+    ] {
+        let mut deps = mock_deps();
+        setup(&mut deps, Condition::Voted { poll_id, vote });
+        let result = exec_default(&mut deps, qualifier_env());
+        assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+    }
+
+    let mut deps = mock_deps();
+    setup(
+        &mut deps,
+        Condition::Voted {
+            poll_id: 1,
+            vote: Some(VoteOption::No),
+        },
+    );
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(
+        result.reason,
+        Some(
+            FailureReason::NotVoted {
+                poll_id: 1,
+                required_vote: Some(VoteOption::No),
+                actual_vote: Some(VoteOption::Yes),
+            }
+            .to_json_string()
+            .unwrap()
+        )
+    );
+
+    let mut deps = mock_deps();
+    setup(
+        &mut deps,
+        Condition::Voted {
+            poll_id: 2,
+            vote: None,
+        },
+    );
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(
+        result.reason,
+        Some(
+            FailureReason::NotVoted {
+                poll_id: 2,
+                required_vote: None,
+                actual_vote: None,
+            }
+            .to_json_string()
+            .unwrap()
+        )
+    );
+}
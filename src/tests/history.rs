@@ -0,0 +1,207 @@
+use cosmwasm_std::testing::mock_info;
+use cosmwasm_std::Addr;
+use valkyrie_qualifier::{QualificationMsg, QualifiedContinueOption};
+
+use crate::msgs::{ConfigureMsg, FailureReason};
+use crate::queries;
+use crate::states::{Condition, QualificationOutcome, QualificationRecord, Requirement};
+use crate::tests::qualify::{
+    exec, exec_default, instantiate_with_requirement, restrict_to_campaign,
+};
+use crate::tests::{
+    exec_configure, mock_deps, mock_participation_count, mock_pool_deposit, qualifier_env,
+    MockDeps, CAMPAIGN, TESTER,
+};
+
+const ACTOR: &str = "terra1jwf5w8rnwvhvdhvwyyqn2uwwfx0wx9s7pcyqlj";
+
+fn setup(deps: &mut MockDeps) {
+    mock_participation_count(deps, 0);
+    mock_pool_deposit(deps, 0);
+
+    instantiate_with_requirement(
+        deps,
+        Requirement::Condition(Condition::ParticipationCount { max: 1 }),
+        None,
+    );
+}
+
+fn campaign_records(deps: &MockDeps) -> Vec<QualificationRecord> {
+    queries::qualifications_by_campaign(
+        deps.as_ref(),
+        qualifier_env(),
+        CAMPAIGN.to_string(),
+        None,
+        None,
+    )
+    .unwrap()
+    .records
+}
+
+fn record_ids(records: &[QualificationRecord]) -> Vec<u64> {
+    records.iter().map(|r| r.id).collect()
+}
+
+#[test]
+fn record_qualifications() {
+    let mut deps = mock_deps();
+    setup(&mut deps);
+    restrict_to_campaign(&mut deps);
+    let env = qualifier_env();
+
+    exec_default(&mut deps, env.clone());
+    exec(
+        &mut deps,
+        env.clone(),
+        mock_info(CAMPAIGN, &[]),
+        CAMPAIGN.to_string(),
+        TESTER.to_string(),
+        ACTOR.to_string(),
+        Some(TESTER.to_string()),
+    )
+    .unwrap();
+    mock_participation_count(&mut deps, 1);
+    exec_default(&mut deps, env.clone());
+
+    let response =
+        queries::qualifications_by_actor(deps.as_ref(), env.clone(), ACTOR.to_string(), None, None)
+            .unwrap();
+    assert_eq!(
+        response.records,
+        vec![QualificationRecord {
+            id: 2,
+            campaign: Addr::unchecked(CAMPAIGN),
+            sender: Addr::unchecked(TESTER),
+            actor: Addr::unchecked(ACTOR),
+            referrer: Some(Addr::unchecked(TESTER)),
+            height: env.block.height,
+            time: env.block.time,
+            continue_option: QualifiedContinueOption::Eligible,
+            reason: None,
+        }]
+    );
+
+    let response = queries::qualifications_by_actor(
+        deps.as_ref(),
+        env.clone(),
+        TESTER.to_string(),
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(record_ids(&response.records), vec![1, 3]);
+    assert_eq!(
+        response.records[1].reason,
+        Some(FailureReason::AlreadyParticipated {
+            participation_count: 1
+        })
+    );
+
+    let response = queries::qualifications_by_outcome(
+        deps.as_ref(),
+        env.clone(),
+        QualificationOutcome::Failed,
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(record_ids(&response.records), vec![3]);
+
+    // paginate by record id
+    let response = queries::qualifications_by_campaign(
+        deps.as_ref(),
+        env.clone(),
+        CAMPAIGN.to_string(),
+        None,
+        Some(2),
+    )
+    .unwrap();
+    assert_eq!(record_ids(&response.records), vec![1, 2]);
+
+    let response = queries::qualifications_by_campaign(
+        deps.as_ref(),
+        env,
+        CAMPAIGN.to_string(),
+        Some(2),
+        None,
+    )
+    .unwrap();
+    assert_eq!(record_ids(&response.records), vec![3]);
+}
+
+#[test]
+fn query_does_not_record() {
+    let mut deps = mock_deps();
+    setup(&mut deps);
+    let env = qualifier_env();
+
+    queries::qualify(
+        deps.as_ref(),
+        env.clone(),
+        QualificationMsg {
+            campaign: CAMPAIGN.to_string(),
+            sender: TESTER.to_string(),
+            actor: TESTER.to_string(),
+            referrer: None,
+        },
+    )
+    .unwrap();
+
+    assert!(campaign_records(&deps).is_empty());
+}
+
+#[test]
+fn record_registered_campaign_only() {
+    let mut deps = mock_deps();
+    setup(&mut deps);
+    let env = qualifier_env();
+
+    // forged by a third party
+    exec(
+        &mut deps,
+        env.clone(),
+        mock_info(TESTER, &[]),
+        CAMPAIGN.to_string(),
+        TESTER.to_string(),
+        TESTER.to_string(),
+        None,
+    )
+    .unwrap();
+    // not registered
+    exec_default(&mut deps, env.clone());
+    assert!(campaign_records(&deps).is_empty());
+
+    restrict_to_campaign(&mut deps);
+    exec_default(&mut deps, env);
+    assert_eq!(record_ids(&campaign_records(&deps)), vec![1]);
+}
+
+#[test]
+fn index_failures_continued_as_eligible() {
+    let mut deps = mock_deps();
+    setup(&mut deps);
+    restrict_to_campaign(&mut deps);
+    exec_configure(
+        &mut deps,
+        ConfigureMsg {
+            continue_option_on_fail: Some(QualifiedContinueOption::Eligible),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    exec_default(&mut deps, qualifier_env());
+    mock_participation_count(&mut deps, 1);
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+
+    for (outcome, ids) in [
+        (QualificationOutcome::Passed, vec![1]),
+        (QualificationOutcome::Failed, vec![2]),
+    ] {
+        let response =
+            queries::qualifications_by_outcome(deps.as_ref(), qualifier_env(), outcome, None, None)
+                .unwrap();
+        assert_eq!(record_ids(&response.records), ids);
+    }
+}
@@ -0,0 +1,93 @@
+use cosmwasm_std::{coin, from_binary, Uint128};
+use cw20::Denom;
+use valkyrie_qualifier::{QualificationResult, QualifiedContinueOption};
+
+use crate::msgs::{ConfigureMsg, FailureReason};
+use crate::states::{Condition, Holder, HolderPolicy, Requirement};
+use crate::tests::qualify::exec;
+use crate::tests::{
+    exec_configure, mock_deps, qualifier_creator_sender, qualifier_env, setup_requirement,
+    MockDeps, CAMPAIGN, TESTER,
+};
+
+const ACTOR: &str = "terra1jwf5w8rnwvhvdhvwyyqn2uwwfx0wx9s7pcyqlj";
+const BALANCE_AMOUNT: u128 = 1000000u128;
+
+fn setup(deps: &mut MockDeps, holder_policy: HolderPolicy, actor_balance: u128) {
+    deps.querier
+        .update_balance(TESTER.to_string(), vec![coin(BALANCE_AMOUNT, "uusd")]);
+    deps.querier
+        .update_balance(ACTOR.to_string(), vec![coin(actor_balance, "uusd")]);
+
+    setup_requirement(
+        deps,
+        Requirement::Condition(Condition::Balance {
+            denom: Denom::Native("uusd".to_string()),
+            min: Uint128::from(BALANCE_AMOUNT),
+            holder: Holder::Sender,
+        }),
+    );
+    exec_configure(
+        deps,
+        ConfigureMsg {
+            holder_policy: Some(holder_policy),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+}
+
+fn exec_with_actor(deps: &mut MockDeps) -> QualificationResult {
+    let response = exec(
+        deps,
+        qualifier_env(),
+        qualifier_creator_sender(),
+        CAMPAIGN.to_string(),
+        TESTER.to_string(),
+        ACTOR.to_string(),
+        None,
+    )
+    .unwrap();
+
+    from_binary(&response.data.unwrap()).unwrap()
+}
+
+#[test]
+fn check_holders() {
+    let low_balance = Some(
+        FailureReason::BalanceTooLow {
+            denom: "uusd".to_string(),
+            required: Uint128::from(BALANCE_AMOUNT),
+            actual: Uint128::zero(),
+        }
+        .to_json_string()
+        .unwrap(),
+    );
+
+    for (holder_policy, actor_balance, reason) in [
+        (HolderPolicy::Sender, 0, None),
+        (HolderPolicy::Actor, 0, low_balance.clone()),
+        (HolderPolicy::Actor, BALANCE_AMOUNT, None),
+        (HolderPolicy::Both, 0, low_balance),
+        (HolderPolicy::Both, BALANCE_AMOUNT, None),
+    ] {
+        let mut deps = mock_deps();
+        setup(&mut deps, holder_policy, actor_balance);
+
+        let result = exec_with_actor(&mut deps);
+        assert_eq!(result.reason, reason);
+    }
+}
+
+#[test]
+fn require_same_address() {
+    let mut deps = mock_deps();
+    setup(&mut deps, HolderPolicy::SameAddress, BALANCE_AMOUNT);
+
+    let result = exec_with_actor(&mut deps);
+    assert_eq!(result.continue_option, QualifiedContinueOption::Ineligible);
+    assert_eq!(
+        result.reason,
+        Some(FailureReason::SenderNotActor {}.to_json_string().unwrap())
+    );
+}
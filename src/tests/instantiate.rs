@@ -1,20 +1,36 @@
+use cosmwasm_bignumber::Decimal256;
 use cosmwasm_std::{Addr, Api, Env, MessageInfo, Response};
+use cw0::{Duration, Expiration};
 use valkyrie_qualifier::QualifiedContinueOption;
 
+use crate::errors::ContractError;
 use crate::executions::{instantiate, ExecuteResult};
-use crate::msgs::InstantiateMsg;
-use crate::states::QualifierConfig;
+use crate::msgs::{InstantiateMsg, PoolInfo};
+use crate::states::{AllowlistPolicy, HolderPolicy, PoolConfig, QualifierConfig};
 use crate::tests::{
-    mock_deps, qualifier_creator_sender, qualifier_env, MockDeps, GOV, POOL, QUALIFIER_CREATOR,
+    default_requirement, mock_deps, qualifier_creator_sender, qualifier_env, MockDeps, GOV, POOL,
+    QUALIFIER_CREATOR,
 };
 
 pub fn exec(deps: &mut MockDeps, env: Env, info: MessageInfo) -> ExecuteResult {
     let msg = InstantiateMsg {
-        pool: POOL.to_string(),
+        pools: vec![PoolInfo {
+            address: POOL.to_string(),
+            weight: None,
+        }],
         gov: GOV.to_string(),
-        deposit_delta: Default::default(),
-        min_mine_stake_amount: Default::default(),
+        requirement: default_requirement(0, 0),
         continue_option_on_fail: QualifiedContinueOption::Eligible,
+        continue_option_on_referrer_fail: None,
+        prepare_validity: None,
+        campaign_restricted: None,
+        snapshot_denoms: None,
+        holder_policy: None,
+        allowlist_policy: None,
+        participant_cap: None,
+        start_time: None,
+        end_time: None,
+        continue_option_on_window_fail: None,
     };
     instantiate(deps.as_mut(), env, info, msg)
 }
@@ -43,9 +59,127 @@ fn succeed() {
         config,
         QualifierConfig {
             admin: Addr::unchecked(QUALIFIER_CREATOR),
-            pool: deps.api.addr_validate(POOL).unwrap(),
+            pools: vec![PoolConfig {
+                address: deps.api.addr_validate(POOL).unwrap(),
+                weight: Decimal256::one(),
+            }],
             gov: deps.api.addr_validate(GOV).unwrap(),
-            continue_option_on_fail: QualifiedContinueOption::Eligible
+            continue_option_on_fail: QualifiedContinueOption::Eligible,
+            continue_option_on_referrer_fail: None,
+            prepare_validity: Duration::Height(0),
+            campaign_restricted: false,
+            snapshot_denoms: vec![],
+            holder_policy: HolderPolicy::Sender,
+            allowlist_policy: AllowlistPolicy::SkipStakeAndDeposit,
+            participant_cap: None,
+            start_time: None,
+            end_time: None,
+            continue_option_on_window_fail: None,
         }
     )
 }
+
+#[test]
+fn failed_with_invalid_pools() {
+    let mut deps = mock_deps();
+
+    let msg = InstantiateMsg {
+        pools: vec![],
+        gov: GOV.to_string(),
+        requirement: default_requirement(0, 0),
+        continue_option_on_fail: QualifiedContinueOption::Eligible,
+        continue_option_on_referrer_fail: None,
+        prepare_validity: None,
+        campaign_restricted: None,
+        snapshot_denoms: None,
+        holder_policy: None,
+        allowlist_policy: None,
+        participant_cap: None,
+        start_time: None,
+        end_time: None,
+        continue_option_on_window_fail: None,
+    };
+
+    let result = instantiate(
+        deps.as_mut(),
+        qualifier_env(),
+        qualifier_creator_sender(),
+        msg.clone(),
+    );
+    assert_eq!(
+        result,
+        Err(ContractError::InvalidArgument {
+            msg: "At least one pool is required".to_string()
+        })
+    );
+
+    let pool = PoolInfo {
+        address: POOL.to_string(),
+        weight: None,
+    };
+    let result = instantiate(
+        deps.as_mut(),
+        qualifier_env(),
+        qualifier_creator_sender(),
+        InstantiateMsg {
+            pools: vec![pool.clone(), pool],
+            ..msg.clone()
+        },
+    );
+    assert_eq!(
+        result,
+        Err(ContractError::InvalidArgument {
+            msg: format!("Duplicated pool: {}", POOL)
+        })
+    );
+
+    let result = instantiate(
+        deps.as_mut(),
+        qualifier_env(),
+        qualifier_creator_sender(),
+        InstantiateMsg {
+            pools: vec![PoolInfo {
+                address: POOL.to_string(),
+                weight: Some(Decimal256::zero()),
+            }],
+            ..msg
+        },
+    );
+    assert_eq!(result, Err(ContractError::InvalidZeroAmount {}));
+}
+
+#[test]
+fn failed_with_invalid_window() {
+    let mut deps = mock_deps();
+
+    let result = instantiate(
+        deps.as_mut(),
+        qualifier_env(),
+        qualifier_creator_sender(),
+        InstantiateMsg {
+            pools: vec![PoolInfo {
+                address: POOL.to_string(),
+                weight: None,
+            }],
+            gov: GOV.to_string(),
+            requirement: default_requirement(0, 0),
+            continue_option_on_fail: QualifiedContinueOption::Eligible,
+            continue_option_on_referrer_fail: None,
+            prepare_validity: None,
+            campaign_restricted: None,
+            snapshot_denoms: None,
+            holder_policy: None,
+            allowlist_policy: None,
+            participant_cap: None,
+            start_time: Some(Expiration::AtHeight(200)),
+            end_time: Some(Expiration::AtHeight(100)),
+            continue_option_on_window_fail: None,
+        },
+    );
+    assert_eq!(
+        result,
+        Err(ContractError::InvalidArgument {
+            msg: "Start time must be before end time".to_string()
+        })
+    );
+}
@@ -0,0 +1,175 @@
+use cosmwasm_std::testing::mock_info;
+use cosmwasm_std::Response;
+use valkyrie_qualifier::QualifiedContinueOption;
+
+use crate::errors::ContractError;
+use crate::executions::{add_merkle_root, register_merkle_proof, remove_merkle_root};
+use crate::merkle;
+use crate::msgs::{FailureReason, MerkleRegistrationResponse};
+use crate::queries;
+use crate::states::{Condition, Requirement};
+use crate::tests::qualify::{exec_default, instantiate_with_requirement};
+use crate::tests::{
+    mock_deps, mock_participation_count, mock_pool_deposit, qualifier_creator_sender,
+    qualifier_env, MockDeps, CAMPAIGN, GOV, POOL, POOL2, TESTER,
+};
+
+fn addresses() -> Vec<String> {
+    vec![GOV, POOL, TESTER, POOL2, CAMPAIGN]
+        .into_iter()
+        .map(|a| a.to_string())
+        .collect()
+}
+
+fn hex_proof(address: &str) -> Vec<String> {
+    merkle::build_proof(&addresses(), address)
+        .unwrap()
+        .iter()
+        .map(merkle::to_hex)
+        .collect()
+}
+
+fn setup(deps: &mut MockDeps) -> String {
+    mock_participation_count(deps, 0);
+    mock_pool_deposit(deps, 0);
+
+    instantiate_with_requirement(
+        deps,
+        Requirement::Condition(Condition::MerkleListed {}),
+        None,
+    );
+
+    let root = merkle::to_hex(&merkle::build_root(&addresses()).unwrap());
+    add_merkle_root(
+        deps.as_mut(),
+        qualifier_env(),
+        qualifier_creator_sender(),
+        CAMPAIGN.to_string(),
+        root.clone(),
+    )
+    .unwrap();
+
+    root
+}
+
+#[test]
+fn build_and_verify() {
+    let addresses = addresses();
+    let root = merkle::build_root(&addresses).unwrap();
+
+    for address in addresses.iter() {
+        let proof = merkle::build_proof(&addresses, address).unwrap();
+        assert!(merkle::verify(&root, address, &proof));
+        assert!(!merkle::verify(&root, "terra1unknown", &proof));
+    }
+    assert_eq!(merkle::build_proof(&addresses, "terra1unknown"), None);
+    assert_eq!(merkle::build_root::<String>(&[]), None);
+    assert_eq!(merkle::from_hex(merkle::to_hex(&root).as_str()), Some(root));
+}
+
+#[test]
+fn register_and_qualify() {
+    let mut deps = mock_deps();
+    let root = setup(&mut deps);
+
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(
+        result.reason,
+        Some(FailureReason::NotMerkleListed {}.to_json_string().unwrap())
+    );
+
+    let response = register_merkle_proof(
+        deps.as_mut(),
+        qualifier_env(),
+        mock_info(TESTER, &[]),
+        CAMPAIGN.to_string(),
+        root.clone(),
+        hex_proof(TESTER),
+    )
+    .unwrap();
+    assert_eq!(
+        response,
+        Response::default()
+            .add_attribute("action", "register_merkle_proof")
+            .add_attribute("campaign", CAMPAIGN)
+            .add_attribute("address", TESTER)
+            .add_attribute("root", root.clone())
+    );
+
+    let response = queries::merkle_registration(
+        deps.as_ref(),
+        qualifier_env(),
+        CAMPAIGN.to_string(),
+        TESTER.to_string(),
+    )
+    .unwrap();
+    assert_eq!(
+        response,
+        MerkleRegistrationResponse {
+            campaign: CAMPAIGN.to_string(),
+            address: TESTER.to_string(),
+            root: Some(root.clone()),
+        }
+    );
+
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+
+    // registration is invalidated with the root
+    remove_merkle_root(
+        deps.as_mut(),
+        qualifier_env(),
+        qualifier_creator_sender(),
+        CAMPAIGN.to_string(),
+        root,
+    )
+    .unwrap();
+    let response = queries::merkle_roots(
+        deps.as_ref(),
+        qualifier_env(),
+        CAMPAIGN.to_string(),
+        None,
+        None,
+    )
+    .unwrap();
+    assert!(response.roots.is_empty());
+
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(result.continue_option, QualifiedContinueOption::Ineligible);
+}
+
+#[test]
+fn invalid_proof() {
+    let mut deps = mock_deps();
+    let root = setup(&mut deps);
+
+    // proof of another address
+    let result = register_merkle_proof(
+        deps.as_mut(),
+        qualifier_env(),
+        mock_info(TESTER, &[]),
+        CAMPAIGN.to_string(),
+        root.clone(),
+        hex_proof(GOV),
+    );
+    assert!(matches!(result, Err(ContractError::InvalidMerkleProof {})));
+
+    let result = register_merkle_proof(
+        deps.as_mut(),
+        qualifier_env(),
+        mock_info(TESTER, &[]),
+        CAMPAIGN.to_string(),
+        merkle::to_hex(&[0; 32]),
+        hex_proof(TESTER),
+    );
+    assert!(matches!(result, Err(ContractError::InvalidArgument { .. })));
+
+    let result = add_merkle_root(
+        deps.as_mut(),
+        qualifier_env(),
+        mock_info(TESTER, &[]),
+        CAMPAIGN.to_string(),
+        root,
+    );
+    assert!(matches!(result, Err(ContractError::Unauthorized {})));
+}
@@ -0,0 +1,89 @@
+use cosmwasm_bignumber::Decimal256;
+use cosmwasm_std::Uint128;
+use valkyrie_qualifier::QualifiedContinueOption;
+
+use crate::errors::ContractError;
+use crate::executions::update_requirement;
+use crate::msgs::{FailureReason, GovStakerResponse, GovStateResponse};
+use crate::states::{Condition, Requirement, ShareThreshold};
+use crate::tests::qualify::exec_default;
+use crate::tests::{
+    mock_deps, mock_gov, qualifier_creator_sender, qualifier_env, setup_requirement, MockDeps,
+};
+
+const SHARE: u64 = 1000u64;
+const TOTAL_SHARE: u64 = 4000u64;
+
+fn setup(deps: &mut MockDeps, min: ShareThreshold) {
+    mock_gov(
+        deps,
+        GovStateResponse {
+            poll_count: 0,
+            total_share: Uint128::from(TOTAL_SHARE),
+            total_deposit: Uint128::zero(),
+        },
+        GovStakerResponse {
+            balance: Uint128::from(SHARE * 2),
+            share: Uint128::from(SHARE),
+            locked_balance: vec![],
+        },
+    );
+
+    setup_requirement(deps, Requirement::Condition(Condition::MineShare { min }));
+}
+
+#[test]
+fn share_amount() {
+    let mut deps = mock_deps();
+    setup(&mut deps, ShareThreshold::Amount(Uint128::from(SHARE)));
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+
+    let mut deps = mock_deps();
+    setup(&mut deps, ShareThreshold::Amount(Uint128::from(SHARE + 1)));
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(
+        result.reason,
+        Some(
+            FailureReason::MineShareTooLow {
+                required: Uint128::from(SHARE + 1),
+                actual: Uint128::from(SHARE),
+            }
+            .to_json_string()
+            .unwrap()
+        )
+    );
+}
+
+#[test]
+fn share_ratio() {
+    let mut deps = mock_deps();
+    setup(&mut deps, ShareThreshold::Ratio(Decimal256::percent(25)));
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+
+    let mut deps = mock_deps();
+    setup(&mut deps, ShareThreshold::Ratio(Decimal256::percent(30)));
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(
+        result.reason,
+        Some(
+            FailureReason::MineShareRatioTooLow {
+                required: Decimal256::percent(30),
+                actual: Decimal256::percent(25),
+            }
+            .to_json_string()
+            .unwrap()
+        )
+    );
+
+    let result = update_requirement(
+        deps.as_mut(),
+        qualifier_env(),
+        qualifier_creator_sender(),
+        Requirement::Condition(Condition::MineShare {
+            min: ShareThreshold::Ratio(Decimal256::percent(101)),
+        }),
+    );
+    assert!(matches!(result, Err(ContractError::InvalidArgument { .. })));
+}
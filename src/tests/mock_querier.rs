@@ -64,6 +64,11 @@ impl CustomMockWasmQuerier {
         self.wasm_raw_query_handlers.insert(address, handler);
     }
 
+    #[allow(dead_code)]
+    pub fn update_balance(&mut self, address: String, balance: Vec<Coin>) {
+        self.base.update_balance(address, balance);
+    }
+
     fn handle_query(&self, request: &QueryRequest<TerraQueryWrapper>) -> QuerierResult {
         match request {
             QueryRequest::Wasm(wasm_request) => match wasm_request {
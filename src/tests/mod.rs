@@ -1,12 +1,41 @@
+use cosmwasm_bignumber::Uint256;
 use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
-use cosmwasm_std::{Addr, Env, MessageInfo, OwnedDeps};
+use cosmwasm_std::{from_binary, to_binary, Addr, Binary, Env, MessageInfo, OwnedDeps, Uint128};
+use valkyrie::campaign::query_msgs::ActorResponse;
 
+use crate::executions::{configure, ExecuteResult};
+use crate::msgs::{
+    ConfigureMsg, GovQueryMsg, GovStakerResponse, GovStateResponse, PoolBalanceOfResponse,
+    PoolQueryMsg,
+};
+use crate::states::{Condition, Requirement, StakeMode};
 use crate::tests::mock_querier::{mock_dependencies, CustomMockWasmQuerier};
 
+pub mod access_list;
+pub mod balance;
+pub mod balance_delta;
+pub mod campaign;
+pub mod campaign_profile;
+pub mod evaluate;
+pub mod governance;
+pub mod history;
+pub mod holder_policy;
 pub mod instantiate;
+pub mod merkle;
+pub mod mine_share;
 pub mod mock_querier;
+pub mod participation;
 pub mod prepare;
+pub mod prune_snapshots;
 pub mod qualify;
+pub mod query;
+pub mod quota;
+pub mod referrer;
+pub mod requirement;
+pub mod stake_mode;
+pub mod stats;
+pub mod update_requirement;
+pub mod window;
 
 type MockDeps = OwnedDeps<MockStorage, MockApi, CustomMockWasmQuerier>;
 
@@ -21,6 +50,7 @@ const TESTER: &str = "terra199vw7724lzkwz6lf2hsx04lrxfkz09tg8dlp6r";
 
 const GOV: &str = "terra1x46rqay4d3cssq8gxxvqz8xt6nwlz4td20k38v";
 const POOL: &str = "terra17lmam6zguazs5q5u6z5mmx76uj63gldnse2pdp";
+const POOL2: &str = "terra1z3sf42ywpuhxdh78rr5vyqxpaxa0dx657x5trs";
 const CAMPAIGN: &str = "terra1757tkx08n0cqrw7p86ny9lnxsqeth0wgp0em95";
 
 fn qualifier_env() -> Env {
@@ -34,3 +64,119 @@ fn qualifier_env() -> Env {
 fn qualifier_creator_sender() -> MessageInfo {
     mock_info(QUALIFIER_CREATOR, &[])
 }
+
+fn exec_configure(deps: &mut MockDeps, msg: ConfigureMsg) -> ExecuteResult {
+    configure(
+        deps.as_mut(),
+        qualifier_env(),
+        qualifier_creator_sender(),
+        msg,
+    )
+}
+
+fn default_requirement(deposit_delta: u64, min_mine_stake_amount: u64) -> Requirement {
+    Requirement::And(vec![
+        Requirement::Condition(Condition::DepositDelta {
+            min: Uint256::from(deposit_delta),
+        }),
+        Requirement::Condition(Condition::MineStake {
+            min: Uint256::from(min_mine_stake_amount),
+            mode: StakeMode::Total,
+        }),
+        Requirement::Condition(Condition::ParticipationCount { max: 1 }),
+    ])
+}
+
+/// Instantiates with the requirement and prepares `TESTER` without deposit or participation
+fn setup_requirement(deps: &mut MockDeps, requirement: Requirement) {
+    mock_participation_count(deps, 0);
+    mock_pool_deposit(deps, 0);
+
+    qualify::instantiate_with_requirement(deps, requirement, None);
+
+    prepare::exec(deps, qualifier_env(), mock_info(TESTER, &[])).unwrap();
+}
+
+fn mock_pool_deposit(deps: &mut MockDeps, amount: u64) {
+    mock_pool_deposit_of(deps, POOL, amount)
+}
+
+fn mock_pool_deposit_of(deps: &mut MockDeps, pool: &str, amount: u64) {
+    deps.querier.register_wasm_smart_query_handler(
+        pool.to_string(),
+        Box::new(move |x| match from_binary::<PoolQueryMsg>(x).unwrap() {
+            PoolQueryMsg::BalanceOf { .. } => to_binary(&PoolBalanceOfResponse {
+                amount: Uint256::from(amount),
+            }),
+        }),
+    );
+}
+
+fn mock_gov_stake(deps: &mut MockDeps, balance: u64) {
+    mock_gov_staker(
+        deps,
+        GovStakerResponse {
+            balance: Uint128::from(balance),
+            share: Default::default(),
+            locked_balance: vec![],
+        },
+    );
+}
+
+fn mock_gov_staker(deps: &mut MockDeps, staker: GovStakerResponse) {
+    mock_gov(deps, GovStateResponse::default(), staker);
+}
+
+fn mock_gov(deps: &mut MockDeps, state: GovStateResponse, staker: GovStakerResponse) {
+    deps.querier.register_wasm_smart_query_handler(
+        GOV.to_string(),
+        Box::new(move |x| match from_binary::<GovQueryMsg>(x).unwrap() {
+            GovQueryMsg::State {} => to_binary(&state),
+            GovQueryMsg::Staker { .. } => to_binary(&staker),
+        }),
+    );
+}
+
+fn mock_actors(deps: &mut MockDeps, actors: Vec<ActorResponse>) {
+    deps.querier.register_wasm_smart_query_handler(
+        CAMPAIGN.to_string(),
+        Box::new(move |x| {
+            match from_binary::<valkyrie::campaign::query_msgs::QueryMsg>(x).unwrap() {
+                valkyrie::campaign::query_msgs::QueryMsg::Actor { address } => to_binary(
+                    &actors
+                        .iter()
+                        .find(|a| a.address == address)
+                        .cloned()
+                        .unwrap_or_else(|| ActorResponse::new(address, None)),
+                ),
+                _ => Ok(Binary::default()),
+            }
+        }),
+    );
+}
+
+fn mock_participation_count(deps: &mut MockDeps, participation_count: u64) {
+    deps.querier.register_wasm_smart_query_handler(
+        CAMPAIGN.to_string(),
+        Box::new(move |x| {
+            match from_binary::<valkyrie::campaign::query_msgs::QueryMsg>(x).unwrap() {
+                valkyrie::campaign::query_msgs::QueryMsg::Actor { .. } => {
+                    to_binary(&ActorResponse {
+                        address: "".to_string(),
+                        referrer_address: None,
+                        participation_reward_amount: Default::default(),
+                        referral_reward_amount: Default::default(),
+                        participation_reward_amounts: vec![],
+                        referral_reward_amounts: vec![],
+                        cumulative_participation_reward_amount: Default::default(),
+                        cumulative_referral_reward_amount: Default::default(),
+                        participation_count,
+                        referral_count: 0,
+                        last_participated_at: Default::default(),
+                    })
+                }
+                _ => Ok(Binary::default()),
+            }
+        }),
+    );
+}
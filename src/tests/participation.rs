@@ -0,0 +1,90 @@
+use valkyrie::campaign::query_msgs::ActorResponse;
+use valkyrie_qualifier::QualifiedContinueOption;
+
+use crate::errors::ContractError;
+use crate::executions::update_requirement;
+use crate::msgs::FailureReason;
+use crate::states::{Condition, Requirement};
+use crate::tests::qualify::exec_default;
+use crate::tests::{
+    mock_actors, mock_deps, qualifier_creator_sender, qualifier_env, setup_requirement, MockDeps,
+    TESTER,
+};
+
+const COOLDOWN: u64 = 3600u64;
+
+fn setup(deps: &mut MockDeps, participation_count: u64, elapsed: u64) {
+    setup_requirement(
+        deps,
+        Requirement::And(vec![
+            Requirement::Condition(Condition::ParticipationCount { max: 2 }),
+            Requirement::Condition(Condition::ParticipationCooldown { seconds: COOLDOWN }),
+        ]),
+    );
+
+    let mut actor = ActorResponse::new(TESTER.to_string(), None);
+    actor.participation_count = participation_count;
+    actor.last_participated_at = qualifier_env().block.time.minus_seconds(elapsed);
+    mock_actors(deps, vec![actor]);
+}
+
+#[test]
+fn succeed() {
+    let mut deps = mock_deps();
+    setup(&mut deps, 0, 0);
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+
+    // participates for the second and last time
+    let mut deps = mock_deps();
+    setup(&mut deps, 1, COOLDOWN);
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+}
+
+#[test]
+fn failed() {
+    let mut deps = mock_deps();
+    setup(&mut deps, 2, COOLDOWN);
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(
+        result.reason,
+        Some(
+            FailureReason::AlreadyParticipated {
+                participation_count: 2
+            }
+            .to_json_string()
+            .unwrap()
+        )
+    );
+
+    let mut deps = mock_deps();
+    setup(&mut deps, 1, COOLDOWN - 1);
+    let result = exec_default(&mut deps, qualifier_env());
+    let now = qualifier_env().block.time;
+    assert_eq!(
+        result.reason,
+        Some(
+            FailureReason::InCooldown {
+                last_participated_at: now.minus_seconds(COOLDOWN - 1),
+                eligible_at: now.plus_seconds(1),
+            }
+            .to_json_string()
+            .unwrap()
+        )
+    );
+}
+
+#[test]
+fn reject_too_long_cooldown() {
+    let mut deps = mock_deps();
+    setup(&mut deps, 0, 0);
+
+    let result = update_requirement(
+        deps.as_mut(),
+        qualifier_env(),
+        qualifier_creator_sender(),
+        Requirement::Condition(Condition::ParticipationCooldown { seconds: u64::MAX }),
+    );
+    assert!(matches!(result, Err(ContractError::InvalidArgument { .. })));
+}
@@ -1,9 +1,10 @@
 use cosmwasm_bignumber::Uint256;
 use cosmwasm_std::{from_binary, to_binary, Env, MessageInfo, Response};
+use cw0::Expiration;
 
 use crate::executions::{prepare, ExecuteResult};
 use crate::msgs::{PoolBalanceOfResponse, PoolQueryMsg};
-use crate::states::load_prepare_status;
+use crate::states::{load_prepare_status, PrepareStatus};
 use crate::tests::{mock_deps, MockDeps, POOL};
 
 const DEPOSIT_AMOUNT: u64 = 1000000u64;
@@ -35,5 +36,13 @@ fn succeed() {
 
     let prepare_status =
         load_prepare_status(deps.as_ref().storage, &env.block.height, &info.sender).unwrap();
-    assert_eq!(prepare_status, Uint256::from(DEPOSIT_AMOUNT))
+    assert_eq!(
+        prepare_status,
+        Some(PrepareStatus {
+            height: env.block.height,
+            pool_deposit: Uint256::from(DEPOSIT_AMOUNT),
+            balances: vec![],
+            expiration: Expiration::AtHeight(env.block.height + 1),
+        })
+    )
 }
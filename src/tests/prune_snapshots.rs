@@ -0,0 +1,171 @@
+use cosmwasm_std::testing::mock_info;
+use cosmwasm_std::{attr, Addr, Env, MessageInfo, Response};
+use cw0::Duration;
+
+use crate::executions::{prune_snapshots, ExecuteResult};
+use crate::msgs::ConfigureMsg;
+use crate::states::{load_latest_prepare_status, load_prepare_status};
+use crate::tests::{exec_configure, mock_deps, mock_pool_deposit, MockDeps, TESTER};
+
+const DEPOSIT_AMOUNT: u64 = 1000000u64;
+
+pub fn exec(
+    deps: &mut MockDeps,
+    env: Env,
+    info: MessageInfo,
+    before_height: u64,
+    start_after: Option<(u64, String)>,
+    limit: Option<u32>,
+) -> ExecuteResult {
+    prune_snapshots(deps.as_mut(), env, info, before_height, start_after, limit)
+}
+
+#[test]
+fn succeed() {
+    let mut deps = mock_deps();
+    mock_pool_deposit(&mut deps, DEPOSIT_AMOUNT);
+
+    let (mut env, _, _) = super::instantiate::default(&mut deps);
+    let start_height = env.block.height;
+
+    let addresses = ["addr0000", "addr0001", "addr0002"];
+    for address in addresses.iter() {
+        super::prepare::exec(&mut deps, env.clone(), mock_info(address, &[])).unwrap();
+        env.block.height += 1;
+    }
+
+    // the last snapshot is still valid, so it should not be pruned
+    env.block.height -= 1;
+    let response = exec(
+        &mut deps,
+        env.clone(),
+        mock_info(TESTER, &[]),
+        env.block.height + 1,
+        None,
+        Some(1),
+    )
+    .unwrap();
+    assert_eq!(
+        response,
+        Response::default()
+            .add_attribute("action", "prune_snapshots")
+            .add_attribute("pruned_count", "1")
+            .add_attribute("next_start_after_height", start_height.to_string())
+            .add_attribute("next_start_after_address", addresses[0])
+    );
+
+    let response = exec(
+        &mut deps,
+        env.clone(),
+        mock_info(TESTER, &[]),
+        env.block.height + 1,
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        response,
+        Response::default()
+            .add_attribute("action", "prune_snapshots")
+            .add_attribute("pruned_count", "1")
+    );
+
+    let storage = deps.as_ref().storage;
+    for (i, address) in addresses.iter().enumerate() {
+        let address = Addr::unchecked(*address);
+        let prepare_status =
+            load_prepare_status(storage, &(start_height + i as u64), &address).unwrap();
+        let latest_prepare_status = load_latest_prepare_status(storage, &address).unwrap();
+
+        assert_eq!(prepare_status.is_some(), i == 2);
+        assert_eq!(prepare_status, latest_prepare_status);
+    }
+}
+
+#[test]
+fn skip_valid_snapshots_with_cursor() {
+    let mut deps = mock_deps();
+    mock_pool_deposit(&mut deps, DEPOSIT_AMOUNT);
+
+    let (mut env, _, _) = super::instantiate::default(&mut deps);
+    let start_height = env.block.height;
+
+    // the first snapshot outlives the later ones after the validity is shortened
+    for (address, validity) in [("addr0000", 100), ("addr0001", 0)] {
+        exec_configure(
+            &mut deps,
+            ConfigureMsg {
+                prepare_validity: Some(Duration::Height(validity)),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        super::prepare::exec(&mut deps, env.clone(), mock_info(address, &[])).unwrap();
+        env.block.height += 1;
+    }
+
+    let response = exec(
+        &mut deps,
+        env.clone(),
+        mock_info(TESTER, &[]),
+        env.block.height,
+        None,
+        Some(1),
+    )
+    .unwrap();
+    assert_eq!(
+        response,
+        Response::default()
+            .add_attribute("action", "prune_snapshots")
+            .add_attribute("pruned_count", "0")
+            .add_attribute("next_start_after_height", start_height.to_string())
+            .add_attribute("next_start_after_address", "addr0000")
+    );
+
+    let response = exec(
+        &mut deps,
+        env.clone(),
+        mock_info(TESTER, &[]),
+        env.block.height,
+        Some((start_height, "addr0000".to_string())),
+        Some(1),
+    )
+    .unwrap();
+    assert_eq!(response.attributes[1], attr("pruned_count", "1"));
+
+    let storage = deps.as_ref().storage;
+    assert!(
+        load_latest_prepare_status(storage, &Addr::unchecked("addr0000"))
+            .unwrap()
+            .is_some()
+    );
+    assert_eq!(
+        load_latest_prepare_status(storage, &Addr::unchecked("addr0001")).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn replace_previous_snapshot() {
+    let mut deps = mock_deps();
+    mock_pool_deposit(&mut deps, DEPOSIT_AMOUNT);
+
+    let (mut env, _, _) = super::instantiate::default(&mut deps);
+    let info = mock_info(TESTER, &[]);
+
+    super::prepare::exec(&mut deps, env.clone(), info.clone()).unwrap();
+    env.block.height += 1;
+    super::prepare::exec(&mut deps, env.clone(), info.clone()).unwrap();
+
+    let storage = deps.as_ref().storage;
+    assert_eq!(
+        load_prepare_status(storage, &(env.block.height - 1), &info.sender).unwrap(),
+        None
+    );
+    assert_eq!(
+        load_latest_prepare_status(storage, &info.sender)
+            .unwrap()
+            .map(|s| s.height),
+        Some(env.block.height)
+    );
+}
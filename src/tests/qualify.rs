@@ -1,14 +1,22 @@
-use cosmwasm_bignumber::Uint256;
-use cosmwasm_std::{from_binary, to_binary, Binary, Env, MessageInfo, Response, Uint128};
+use cosmwasm_bignumber::{Decimal256, Uint256};
+use cosmwasm_std::testing::mock_info;
+use cosmwasm_std::{from_binary, to_binary, Addr, Binary, Env, MessageInfo, Response, Uint128};
+use cw0::Duration;
 use valkyrie::campaign::query_msgs::ActorResponse;
 use valkyrie_qualifier::{QualificationMsg, QualificationResult, QualifiedContinueOption};
 
+use crate::errors::ContractError;
 use crate::executions::{instantiate, qualify, ExecuteResult};
 use crate::msgs::{
-    GovQueryMsg, GovStakerResponse, InstantiateMsg, PoolBalanceOfResponse, PoolQueryMsg,
+    ConfigureMsg, FailureReason, GovQueryMsg, GovStakerResponse, InstantiateMsg,
+    PoolBalanceOfResponse, PoolInfo, PoolQueryMsg,
 };
+use crate::queries;
+use crate::states::{load_latest_prepare_status, Requirement};
 use crate::tests::{
-    mock_deps, qualifier_creator_sender, qualifier_env, MockDeps, CAMPAIGN, GOV, POOL, TESTER,
+    default_requirement, exec_configure, mock_deps, mock_gov_stake, mock_participation_count,
+    mock_pool_deposit, mock_pool_deposit_of, qualifier_creator_sender, qualifier_env, MockDeps,
+    CAMPAIGN, GOV, POOL, POOL2, TESTER,
 };
 
 const DEPOSIT_AMOUNT: u64 = 1000000u64;
@@ -39,7 +47,7 @@ pub fn will_success(
     referrer: Option<String>,
 ) -> (Env, MessageInfo, Response) {
     let env = qualifier_env();
-    let info = qualifier_creator_sender();
+    let info = mock_info(CAMPAIGN, &[]);
 
     let response = exec(
         deps,
@@ -55,6 +63,79 @@ pub fn will_success(
     (env, info, response)
 }
 
+pub fn instantiate_with(
+    deps: &mut MockDeps,
+    deposit_delta: u64,
+    min_mine_stake_amount: u64,
+    prepare_validity: Option<Duration>,
+) {
+    instantiate_with_requirement(
+        deps,
+        default_requirement(deposit_delta, min_mine_stake_amount),
+        prepare_validity,
+    )
+}
+
+pub fn instantiate_with_requirement(
+    deps: &mut MockDeps,
+    requirement: Requirement,
+    prepare_validity: Option<Duration>,
+) {
+    instantiate(
+        deps.as_mut(),
+        qualifier_env(),
+        qualifier_creator_sender(),
+        InstantiateMsg {
+            pools: vec![PoolInfo {
+                address: POOL.to_string(),
+                weight: None,
+            }],
+            gov: GOV.to_string(),
+            requirement,
+            continue_option_on_fail: QualifiedContinueOption::Ineligible,
+            continue_option_on_referrer_fail: None,
+            prepare_validity,
+            campaign_restricted: None,
+            snapshot_denoms: None,
+            holder_policy: None,
+            allowlist_policy: None,
+            participant_cap: None,
+            start_time: None,
+            end_time: None,
+            continue_option_on_window_fail: None,
+        },
+    )
+    .unwrap();
+}
+
+/// Registers `CAMPAIGN` and restricts the callers to registered campaigns
+pub fn restrict_to_campaign(deps: &mut MockDeps) {
+    exec_configure(
+        deps,
+        ConfigureMsg {
+            campaign_restricted: Some(true),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    super::campaign::exec_add(deps, qualifier_env(), qualifier_creator_sender(), CAMPAIGN).unwrap();
+}
+
+pub fn exec_default(deps: &mut MockDeps, env: Env) -> QualificationResult {
+    let response = exec(
+        deps,
+        env,
+        mock_info(CAMPAIGN, &[]),
+        CAMPAIGN.to_string(),
+        TESTER.to_string(),
+        TESTER.to_string(),
+        None,
+    )
+    .unwrap();
+
+    from_binary(&response.data.unwrap()).unwrap()
+}
+
 #[test]
 fn succeed() {
     let mut deps = mock_deps();
@@ -65,9 +146,9 @@ fn succeed() {
             GovQueryMsg::Staker { .. } => to_binary(&GovStakerResponse {
                 balance: Uint128::from(DEPOSIT_AMOUNT * 2),
                 share: Default::default(),
-                claimable_airdrop: vec![],
                 locked_balance: vec![],
             }),
+            _ => panic!("unexpected query"),
         }),
     );
 
@@ -112,16 +193,28 @@ fn succeed() {
         env.clone(),
         info.clone(),
         InstantiateMsg {
-            pool: POOL.to_string(),
+            pools: vec![PoolInfo {
+                address: POOL.to_string(),
+                weight: None,
+            }],
             gov: GOV.to_string(),
-            deposit_delta: Uint256::from(DEPOSIT_AMOUNT),
-            min_mine_stake_amount: Uint256::from(DEPOSIT_AMOUNT * 2),
+            requirement: default_requirement(DEPOSIT_AMOUNT, DEPOSIT_AMOUNT * 2),
             continue_option_on_fail: QualifiedContinueOption::Eligible,
+            continue_option_on_referrer_fail: None,
+            prepare_validity: None,
+            campaign_restricted: None,
+            snapshot_denoms: None,
+            holder_policy: None,
+            allowlist_policy: None,
+            participant_cap: None,
+            start_time: None,
+            end_time: None,
+            continue_option_on_window_fail: None,
         },
     )
     .unwrap();
 
-    super::prepare::exec(&mut deps, env.clone(), info.clone()).unwrap();
+    super::prepare::exec(&mut deps, env.clone(), mock_info(TESTER, &[])).unwrap();
 
     // overwrite
     deps.querier.register_wasm_smart_query_handler(
@@ -154,3 +247,307 @@ fn succeed() {
             )
     )
 }
+
+#[test]
+fn succeed_within_prepare_validity() {
+    let mut deps = mock_deps();
+    mock_gov_stake(&mut deps, 0);
+    mock_participation_count(&mut deps, 0);
+    mock_pool_deposit(&mut deps, DEPOSIT_AMOUNT);
+
+    instantiate_with(&mut deps, DEPOSIT_AMOUNT, 0, Some(Duration::Height(5)));
+    restrict_to_campaign(&mut deps);
+
+    let mut env = qualifier_env();
+    super::prepare::exec(&mut deps, env.clone(), mock_info(TESTER, &[])).unwrap();
+
+    mock_pool_deposit(&mut deps, DEPOSIT_AMOUNT * 2);
+
+    env.block.height += 5;
+    let result = exec_default(&mut deps, env.clone());
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+
+    // snapshot is consumed
+    let sender = Addr::unchecked(TESTER);
+    assert_eq!(
+        load_latest_prepare_status(deps.as_ref().storage, &sender).unwrap(),
+        None
+    );
+    let result = exec_default(&mut deps, env);
+    assert_eq!(result.continue_option, QualifiedContinueOption::Ineligible);
+}
+
+#[test]
+fn keep_snapshot_on_forged_qualification() {
+    let mut deps = mock_deps();
+    mock_gov_stake(&mut deps, 0);
+    mock_participation_count(&mut deps, 0);
+    mock_pool_deposit(&mut deps, DEPOSIT_AMOUNT);
+
+    instantiate_with(&mut deps, DEPOSIT_AMOUNT, 0, None);
+
+    let env = qualifier_env();
+    super::prepare::exec(&mut deps, env.clone(), mock_info(TESTER, &[])).unwrap();
+
+    // a third party pretends to qualify on behalf of the campaign,
+    // or names itself as the campaign
+    for campaign in [CAMPAIGN, GOV] {
+        exec(
+            &mut deps,
+            env.clone(),
+            mock_info(GOV, &[]),
+            campaign.to_string(),
+            TESTER.to_string(),
+            TESTER.to_string(),
+            None,
+        )
+        .unwrap();
+
+        let sender = Addr::unchecked(TESTER);
+        assert!(load_latest_prepare_status(deps.as_ref().storage, &sender)
+            .unwrap()
+            .is_some());
+    }
+
+    restrict_to_campaign(&mut deps);
+    mock_pool_deposit(&mut deps, DEPOSIT_AMOUNT * 2);
+    let result = exec_default(&mut deps, env);
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+}
+
+#[test]
+fn failed_with_expired_prepare_status() {
+    let mut deps = mock_deps();
+    mock_gov_stake(&mut deps, 0);
+    mock_participation_count(&mut deps, 0);
+    mock_pool_deposit(&mut deps, DEPOSIT_AMOUNT);
+
+    instantiate_with(&mut deps, DEPOSIT_AMOUNT, 0, Some(Duration::Time(60)));
+
+    let mut env = qualifier_env();
+    super::prepare::exec(&mut deps, env.clone(), mock_info(TESTER, &[])).unwrap();
+
+    mock_pool_deposit(&mut deps, DEPOSIT_AMOUNT * 2);
+
+    env.block.time = env.block.time.plus_seconds(61);
+    let result = exec_default(&mut deps, env);
+    assert_eq!(
+        result,
+        QualificationResult {
+            continue_option: QualifiedContinueOption::Ineligible,
+            reason: Some(
+                FailureReason::PrepareStatusNotFound {}
+                    .to_json_string()
+                    .unwrap()
+            ),
+        }
+    );
+}
+
+#[test]
+fn restrict_to_registered_campaigns() {
+    let mut deps = mock_deps();
+    mock_gov_stake(&mut deps, 0);
+    mock_participation_count(&mut deps, 0);
+    mock_pool_deposit(&mut deps, 0);
+
+    instantiate_with(&mut deps, 0, 0, None);
+
+    let env = qualifier_env();
+    exec_configure(
+        &mut deps,
+        ConfigureMsg {
+            campaign_restricted: Some(true),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    super::prepare::exec(&mut deps, env.clone(), mock_info(TESTER, &[])).unwrap();
+
+    // not registered
+    let result = exec(
+        &mut deps,
+        env.clone(),
+        mock_info(CAMPAIGN, &[]),
+        CAMPAIGN.to_string(),
+        TESTER.to_string(),
+        TESTER.to_string(),
+        None,
+    );
+    assert_eq!(result, Err(ContractError::Unauthorized {}));
+
+    super::campaign::exec_add(&mut deps, env.clone(), qualifier_creator_sender(), CAMPAIGN)
+        .unwrap();
+
+    // sender is not the campaign
+    let result = exec(
+        &mut deps,
+        env.clone(),
+        mock_info(TESTER, &[]),
+        CAMPAIGN.to_string(),
+        TESTER.to_string(),
+        TESTER.to_string(),
+        None,
+    );
+    assert_eq!(result, Err(ContractError::Unauthorized {}));
+
+    exec(
+        &mut deps,
+        env,
+        mock_info(CAMPAIGN, &[]),
+        CAMPAIGN.to_string(),
+        TESTER.to_string(),
+        TESTER.to_string(),
+        None,
+    )
+    .unwrap();
+}
+
+#[test]
+fn failed_with_decreased_deposit() {
+    let mut deps = mock_deps();
+    mock_gov_stake(&mut deps, 0);
+    mock_participation_count(&mut deps, 0);
+    mock_pool_deposit(&mut deps, DEPOSIT_AMOUNT * 2);
+
+    instantiate_with(&mut deps, DEPOSIT_AMOUNT, 0, None);
+
+    let env = qualifier_env();
+    super::prepare::exec(&mut deps, env.clone(), mock_info(TESTER, &[])).unwrap();
+
+    mock_pool_deposit(&mut deps, DEPOSIT_AMOUNT);
+
+    let result = exec_default(&mut deps, env);
+    assert_eq!(
+        result,
+        QualificationResult {
+            continue_option: QualifiedContinueOption::Ineligible,
+            reason: Some(
+                FailureReason::DepositDecreased {
+                    required: Uint256::from(DEPOSIT_AMOUNT),
+                    before: Uint256::from(DEPOSIT_AMOUNT * 2),
+                    after: Uint256::from(DEPOSIT_AMOUNT),
+                }
+                .to_json_string()
+                .unwrap()
+            ),
+        }
+    );
+}
+
+#[test]
+fn failed_with_structured_reason() {
+    let mut deps = mock_deps();
+    mock_gov_stake(&mut deps, DEPOSIT_AMOUNT);
+    mock_participation_count(&mut deps, 0);
+    mock_pool_deposit(&mut deps, 0);
+
+    instantiate_with(&mut deps, 0, DEPOSIT_AMOUNT * 2, None);
+
+    let env = qualifier_env();
+    super::prepare::exec(&mut deps, env.clone(), mock_info(TESTER, &[])).unwrap();
+
+    let response = exec(
+        &mut deps,
+        env,
+        qualifier_creator_sender(),
+        CAMPAIGN.to_string(),
+        TESTER.to_string(),
+        TESTER.to_string(),
+        None,
+    )
+    .unwrap();
+
+    let reason = FailureReason::MineStakeTooLow {
+        required: Uint256::from(DEPOSIT_AMOUNT * 2),
+        actual: Uint256::from(DEPOSIT_AMOUNT),
+    };
+    assert_eq!(
+        reason.to_json_string().unwrap(),
+        r#"{"mine_stake_too_low":{"required":"2000000","actual":"1000000"}}"#
+    );
+    assert_eq!(
+        response,
+        Response::default()
+            .add_attribute("action", "qualify")
+            .add_attribute("qualified_continue_option", "ineligible")
+            .add_attribute("failure_reason", "mine_stake_too_low")
+            .add_attribute("required", "2000000")
+            .add_attribute("actual", "1000000")
+            .set_data(
+                to_binary(&QualificationResult {
+                    continue_option: QualifiedContinueOption::Ineligible,
+                    reason: Some(reason.to_json_string().unwrap()),
+                })
+                .unwrap()
+            )
+    );
+}
+
+#[test]
+fn succeed_with_weighted_pools() {
+    let mut deps = mock_deps();
+    mock_gov_stake(&mut deps, 0);
+    mock_participation_count(&mut deps, 0);
+    mock_pool_deposit(&mut deps, DEPOSIT_AMOUNT);
+    mock_pool_deposit_of(&mut deps, POOL2, 0);
+
+    let env = qualifier_env();
+    instantiate(
+        deps.as_mut(),
+        env.clone(),
+        qualifier_creator_sender(),
+        InstantiateMsg {
+            pools: vec![
+                PoolInfo {
+                    address: POOL.to_string(),
+                    weight: None,
+                },
+                PoolInfo {
+                    address: POOL2.to_string(),
+                    weight: Some(Decimal256::percent(50)),
+                },
+            ],
+            gov: GOV.to_string(),
+            requirement: default_requirement(DEPOSIT_AMOUNT, 0),
+            continue_option_on_fail: QualifiedContinueOption::Ineligible,
+            continue_option_on_referrer_fail: None,
+            prepare_validity: None,
+            campaign_restricted: None,
+            snapshot_denoms: None,
+            holder_policy: None,
+            allowlist_policy: None,
+            participant_cap: None,
+            start_time: None,
+            end_time: None,
+            continue_option_on_window_fail: None,
+        },
+    )
+    .unwrap();
+
+    super::prepare::exec(&mut deps, env.clone(), mock_info(TESTER, &[])).unwrap();
+
+    // half of the deposit is counted
+    mock_pool_deposit_of(&mut deps, POOL2, DEPOSIT_AMOUNT);
+    let result = queries::evaluate(
+        deps.as_ref(),
+        env.clone(),
+        QualificationMsg {
+            campaign: CAMPAIGN.to_string(),
+            sender: TESTER.to_string(),
+            actor: TESTER.to_string(),
+            referrer: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        result.checks[0].actual,
+        Some((DEPOSIT_AMOUNT / 2).to_string())
+    );
+    assert_eq!(result.continue_option, QualifiedContinueOption::Ineligible);
+
+    mock_pool_deposit_of(&mut deps, POOL2, DEPOSIT_AMOUNT * 2);
+    let result = exec_default(&mut deps, env);
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+}
@@ -0,0 +1,116 @@
+use cosmwasm_bignumber::{Decimal256, Uint256};
+use cosmwasm_std::testing::mock_info;
+use cosmwasm_std::{from_binary, to_binary, Addr, Api};
+use cw0::{Duration, Expiration};
+use valkyrie_qualifier::QualifiedContinueOption;
+
+use crate::entrypoints;
+use crate::msgs::{
+    PoolBalanceOfResponse, PoolQueryMsg, PrepareStatusResponse, QueryMsg, StateResponse,
+};
+use crate::states::{AllowlistPolicy, HolderPolicy, PoolConfig, QualifierConfig, Requirement};
+use crate::tests::{mock_deps, GOV, POOL, QUALIFIER_CREATOR, TESTER};
+
+const DEPOSIT_AMOUNT: u64 = 1000000u64;
+
+#[test]
+fn config_and_state() {
+    let mut deps = mock_deps();
+
+    let (env, _, _) = super::instantiate::default(&mut deps);
+
+    let expected_config = QualifierConfig {
+        admin: Addr::unchecked(QUALIFIER_CREATOR),
+        pools: vec![PoolConfig {
+            address: deps.api.addr_validate(POOL).unwrap(),
+            weight: Decimal256::one(),
+        }],
+        gov: deps.api.addr_validate(GOV).unwrap(),
+        continue_option_on_fail: QualifiedContinueOption::Eligible,
+        continue_option_on_referrer_fail: None,
+        prepare_validity: Duration::Height(0),
+        campaign_restricted: false,
+        snapshot_denoms: vec![],
+        holder_policy: HolderPolicy::Sender,
+        allowlist_policy: AllowlistPolicy::SkipStakeAndDeposit,
+        participant_cap: None,
+        start_time: None,
+        end_time: None,
+        continue_option_on_window_fail: None,
+    };
+
+    let config: QualifierConfig =
+        from_binary(&entrypoints::query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap())
+            .unwrap();
+    assert_eq!(config, expected_config);
+
+    let requirement: Requirement = from_binary(
+        &entrypoints::query(deps.as_ref(), env.clone(), QueryMsg::Requirement {}).unwrap(),
+    )
+    .unwrap();
+
+    let state: StateResponse =
+        from_binary(&entrypoints::query(deps.as_ref(), env, QueryMsg::State {}).unwrap()).unwrap();
+    assert_eq!(
+        state,
+        StateResponse {
+            config: expected_config,
+            requirement,
+        }
+    );
+}
+
+#[test]
+fn prepare_status() {
+    let mut deps = mock_deps();
+
+    deps.querier.register_wasm_smart_query_handler(
+        POOL.to_string(),
+        Box::new(|x| match from_binary::<PoolQueryMsg>(x).unwrap() {
+            PoolQueryMsg::BalanceOf { .. } => to_binary(&PoolBalanceOfResponse {
+                amount: Uint256::from(DEPOSIT_AMOUNT),
+            }),
+        }),
+    );
+
+    let (env, _, _) = super::instantiate::default(&mut deps);
+    let info = mock_info(TESTER, &[]);
+    super::prepare::exec(&mut deps, env.clone(), info.clone()).unwrap();
+
+    let response: Option<PrepareStatusResponse> = from_binary(
+        &entrypoints::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::PrepareStatus {
+                address: info.sender.to_string(),
+                height: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        response,
+        Some(PrepareStatusResponse {
+            address: info.sender.to_string(),
+            height: env.block.height,
+            pool_deposit: Uint256::from(DEPOSIT_AMOUNT),
+            balances: vec![],
+            expiration: Expiration::AtHeight(env.block.height + 1),
+        })
+    );
+
+    let response: Option<PrepareStatusResponse> = from_binary(
+        &entrypoints::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::PrepareStatus {
+                address: info.sender.to_string(),
+                height: Some(env.block.height + 1),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(response, None);
+}
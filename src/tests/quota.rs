@@ -0,0 +1,205 @@
+use cosmwasm_std::testing::mock_info;
+use valkyrie_qualifier::{QualificationMsg, QualifiedContinueOption};
+
+use crate::errors::ContractError;
+use crate::executions::set_quota;
+use crate::msgs::{ConfigureMsg, FailureReason, RemainingSlotsResponse};
+use crate::queries;
+use crate::states::{Condition, Quota, Requirement};
+use crate::tests::qualify::{
+    exec, exec_default, instantiate_with_requirement, restrict_to_campaign,
+};
+use crate::tests::{
+    exec_configure, mock_deps, mock_participation_count, mock_pool_deposit,
+    qualifier_creator_sender, qualifier_env, MockDeps, CAMPAIGN, TESTER,
+};
+
+fn setup(deps: &mut MockDeps) {
+    mock_participation_count(deps, 0);
+    mock_pool_deposit(deps, 0);
+
+    instantiate_with_requirement(
+        deps,
+        Requirement::Condition(Condition::ParticipationCount { max: 1 }),
+        None,
+    );
+    restrict_to_campaign(deps);
+}
+
+fn exec_set(deps: &mut MockDeps, campaign: Option<&str>, quota: Option<u64>) {
+    set_quota(
+        deps.as_mut(),
+        qualifier_env(),
+        qualifier_creator_sender(),
+        campaign.map(|c| c.to_string()),
+        quota,
+    )
+    .unwrap();
+}
+
+fn remaining_slots(deps: &MockDeps) -> RemainingSlotsResponse {
+    queries::remaining_slots(deps.as_ref(), qualifier_env(), CAMPAIGN.to_string()).unwrap()
+}
+
+#[test]
+fn campaign_quota() {
+    let mut deps = mock_deps();
+    setup(&mut deps);
+    exec_set(&mut deps, None, Some(3));
+    exec_set(&mut deps, Some(CAMPAIGN), Some(2));
+
+    assert_eq!(
+        remaining_slots(&deps),
+        RemainingSlotsResponse {
+            global: Quota {
+                cap: Some(3),
+                qualified_count: 0,
+            },
+            campaign: Quota {
+                cap: Some(2),
+                qualified_count: 0,
+            },
+            remaining: Some(2),
+        }
+    );
+
+    // the query path does not consume slots
+    let result = queries::qualify_without_checking_deposit(
+        deps.as_ref(),
+        qualifier_env(),
+        QualificationMsg {
+            campaign: CAMPAIGN.to_string(),
+            sender: TESTER.to_string(),
+            actor: TESTER.to_string(),
+            referrer: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+    assert_eq!(remaining_slots(&deps).remaining, Some(2));
+
+    for _ in 0..2 {
+        let result = exec_default(&mut deps, qualifier_env());
+        assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+    }
+    assert_eq!(remaining_slots(&deps).remaining, Some(0));
+
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(result.continue_option, QualifiedContinueOption::Ineligible);
+    assert_eq!(
+        result.reason,
+        Some(
+            FailureReason::QuotaExhausted {
+                quota: 2,
+                campaign: Some(CAMPAIGN.to_string()),
+            }
+            .to_json_string()
+            .unwrap()
+        )
+    );
+
+    exec_set(&mut deps, Some(CAMPAIGN), None);
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(
+        result.reason,
+        Some(
+            FailureReason::QuotaExhausted {
+                quota: 3,
+                campaign: None,
+            }
+            .to_json_string()
+            .unwrap()
+        )
+    );
+}
+
+#[test]
+fn failed_qualification_does_not_count() {
+    let mut deps = mock_deps();
+    setup(&mut deps);
+    exec_set(&mut deps, None, Some(1));
+
+    mock_participation_count(&mut deps, 1);
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(result.continue_option, QualifiedContinueOption::Ineligible);
+    assert_eq!(remaining_slots(&deps).remaining, Some(1));
+
+    let result = set_quota(
+        deps.as_mut(),
+        qualifier_env(),
+        mock_info(TESTER, &[]),
+        None,
+        None,
+    );
+    assert!(matches!(result, Err(ContractError::Unauthorized {})));
+}
+
+#[test]
+fn require_campaign_restriction() {
+    let mut deps = mock_deps();
+    mock_participation_count(&mut deps, 0);
+    mock_pool_deposit(&mut deps, 0);
+    instantiate_with_requirement(
+        &mut deps,
+        Requirement::Condition(Condition::ParticipationCount { max: 1 }),
+        None,
+    );
+
+    for campaign in [None, Some(CAMPAIGN.to_string())] {
+        let result = set_quota(
+            deps.as_mut(),
+            qualifier_env(),
+            qualifier_creator_sender(),
+            campaign,
+            Some(1),
+        );
+        assert!(matches!(result, Err(ContractError::InvalidArgument { .. })));
+    }
+
+    restrict_to_campaign(&mut deps);
+    exec_set(&mut deps, Some(CAMPAIGN), Some(1));
+
+    // the restriction can not be lifted while quotas remain
+    let result = exec_configure(
+        &mut deps,
+        ConfigureMsg {
+            campaign_restricted: Some(false),
+            ..Default::default()
+        },
+    );
+    assert!(matches!(result, Err(ContractError::InvalidArgument { .. })));
+}
+
+#[test]
+fn count_registered_campaign_only() {
+    let mut deps = mock_deps();
+    mock_participation_count(&mut deps, 0);
+    mock_pool_deposit(&mut deps, 0);
+    instantiate_with_requirement(
+        &mut deps,
+        Requirement::Condition(Condition::ParticipationCount { max: 1 }),
+        None,
+    );
+
+    // forged by a third party
+    exec(
+        &mut deps,
+        qualifier_env(),
+        mock_info(TESTER, &[]),
+        CAMPAIGN.to_string(),
+        TESTER.to_string(),
+        TESTER.to_string(),
+        None,
+    )
+    .unwrap();
+    // not registered
+    exec_default(&mut deps, qualifier_env());
+    assert_eq!(remaining_slots(&deps).campaign.qualified_count, 0);
+
+    restrict_to_campaign(&mut deps);
+    exec_default(&mut deps, qualifier_env());
+    assert_eq!(remaining_slots(&deps).campaign.qualified_count, 1);
+}
@@ -0,0 +1,187 @@
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::from_binary;
+use valkyrie::campaign::query_msgs::ActorResponse;
+use valkyrie_qualifier::{QualificationResult, QualifiedContinueOption};
+
+use crate::msgs::{ConfigureMsg, ContinueOptionOverride, FailureReason};
+use crate::states::{Condition, Requirement, StakeMode};
+use crate::tests::qualify::exec;
+use crate::tests::{
+    exec_configure, mock_actors, mock_deps, mock_gov_stake, qualifier_creator_sender,
+    qualifier_env, setup_requirement, MockDeps, CAMPAIGN, TESTER,
+};
+
+const REFERRER: &str = "terra1jwf5w8rnwvhvdhvwyyqn2uwwfx0wx9s7pcyqlj";
+const STAKE_AMOUNT: u64 = 1000000u64;
+
+fn referrer_requirement() -> Requirement {
+    Requirement::And(vec![
+        Requirement::Condition(Condition::ParticipationCount { max: 1 }),
+        Requirement::Condition(Condition::ReferrerNotSelf {}),
+        Requirement::Condition(Condition::ReferrerMineStake {
+            min: Uint256::from(STAKE_AMOUNT),
+            mode: StakeMode::Total,
+        }),
+        Requirement::Condition(Condition::ReferrerParticipated {}),
+        Requirement::Condition(Condition::ReferralCount { max: 4 }),
+    ])
+}
+
+fn setup(deps: &mut MockDeps, stake: u64, participation_count: u64, referral_count: u64) {
+    setup_requirement(deps, referrer_requirement());
+    configure_referrer_continue_option(deps, ContinueOptionOverride::Eligible);
+
+    let mut referrer = ActorResponse::new(REFERRER.to_string(), None);
+    referrer.participation_count = participation_count;
+    referrer.referral_count = referral_count;
+    mock_actors(deps, vec![referrer]);
+    mock_gov_stake(deps, stake);
+}
+
+fn configure_referrer_continue_option(deps: &mut MockDeps, option: ContinueOptionOverride) {
+    exec_configure(
+        deps,
+        ConfigureMsg {
+            continue_option_on_referrer_fail: Some(option),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+}
+
+fn exec_with_referrer(deps: &mut MockDeps, referrer: Option<&str>) -> QualificationResult {
+    let response = exec(
+        deps,
+        qualifier_env(),
+        qualifier_creator_sender(),
+        CAMPAIGN.to_string(),
+        TESTER.to_string(),
+        TESTER.to_string(),
+        referrer.map(|r| r.to_string()),
+    )
+    .unwrap();
+
+    from_binary(&response.data.unwrap()).unwrap()
+}
+
+#[test]
+fn succeed() {
+    let mut deps = mock_deps();
+    // the last referral within the cap
+    setup(&mut deps, STAKE_AMOUNT, 1, 3);
+
+    let result = exec_with_referrer(&mut deps, Some(REFERRER));
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+    assert_eq!(result.reason, None);
+}
+
+#[test]
+fn failed_with_referrer_continue_option() {
+    for (stake, participation_count, referral_count, referrer, reason) in [
+        (STAKE_AMOUNT, 1, 0, TESTER, FailureReason::SelfReferral {}),
+        (
+            STAKE_AMOUNT - 1,
+            1,
+            0,
+            REFERRER,
+            FailureReason::ReferrerMineStakeTooLow {
+                required: Uint256::from(STAKE_AMOUNT),
+                actual: Uint256::from(STAKE_AMOUNT - 1),
+            },
+        ),
+        (
+            STAKE_AMOUNT,
+            0,
+            0,
+            REFERRER,
+            FailureReason::ReferrerNotParticipated {},
+        ),
+        (0, 0, 0, "", FailureReason::ReferrerNotFound {}),
+        (
+            STAKE_AMOUNT,
+            1,
+            4,
+            REFERRER,
+            FailureReason::ReferralCountExceeded { max: 4, actual: 4 },
+        ),
+    ] {
+        let mut deps = mock_deps();
+        setup(&mut deps, stake, participation_count, referral_count);
+
+        let referrer = Some(referrer).filter(|r| !r.is_empty());
+        let result = exec_with_referrer(&mut deps, referrer);
+        assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+        assert_eq!(result.reason, Some(reason.to_json_string().unwrap()));
+    }
+}
+
+#[test]
+fn failed_with_actor_condition() {
+    let mut deps = mock_deps();
+    setup(&mut deps, STAKE_AMOUNT, 1, 0);
+
+    let mut actor = ActorResponse::new(TESTER.to_string(), None);
+    actor.participation_count = 1;
+    let mut referrer = ActorResponse::new(REFERRER.to_string(), None);
+    referrer.participation_count = 1;
+    mock_actors(&mut deps, vec![actor, referrer]);
+
+    let result = exec_with_referrer(&mut deps, Some(REFERRER));
+    assert_eq!(result.continue_option, QualifiedContinueOption::Ineligible);
+    assert_eq!(
+        result.reason,
+        Some(
+            FailureReason::AlreadyParticipated {
+                participation_count: 1
+            }
+            .to_json_string()
+            .unwrap()
+        )
+    );
+}
+
+#[test]
+fn clear_referrer_continue_option() {
+    let mut deps = mock_deps();
+    setup(&mut deps, STAKE_AMOUNT, 0, 0);
+
+    let result = exec_with_referrer(&mut deps, Some(REFERRER));
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+
+    // falls back to `continue_option_on_fail`
+    configure_referrer_continue_option(&mut deps, ContinueOptionOverride::Unset);
+    let result = exec_with_referrer(&mut deps, Some(REFERRER));
+    assert_eq!(result.continue_option, QualifiedContinueOption::Ineligible);
+}
+
+#[test]
+fn failed_with_actor_condition_after_referrer_condition() {
+    let mut deps = mock_deps();
+    setup_requirement(
+        &mut deps,
+        Requirement::And(vec![
+            Requirement::Condition(Condition::ReferrerNotSelf {}),
+            Requirement::Condition(Condition::ParticipationCount { max: 1 }),
+        ]),
+    );
+    configure_referrer_continue_option(&mut deps, ContinueOptionOverride::Eligible);
+
+    let mut actor = ActorResponse::new(TESTER.to_string(), None);
+    actor.participation_count = 5;
+    mock_actors(&mut deps, vec![actor]);
+    mock_gov_stake(&mut deps, 0);
+
+    // self referral does not hide the failure of the actor
+    let result = exec_with_referrer(&mut deps, Some(TESTER));
+    assert_eq!(result.continue_option, QualifiedContinueOption::Ineligible);
+    assert_eq!(
+        result.reason,
+        Some(
+            FailureReason::AlreadyParticipated {
+                participation_count: 5
+            }
+            .to_json_string()
+            .unwrap()
+        )
+    );
+}
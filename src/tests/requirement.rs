@@ -0,0 +1,176 @@
+use cosmwasm_bignumber::Uint256;
+use valkyrie_qualifier::{QualificationMsg, QualifiedContinueOption};
+
+use crate::msgs::FailureReason;
+use crate::queries;
+use crate::states::{Condition, Requirement, StakeMode};
+use crate::tests::qualify::{exec_default, instantiate_with_requirement};
+use crate::tests::{
+    mock_deps, mock_gov_stake, mock_participation_count, mock_pool_deposit, qualifier_env,
+    setup_requirement, MockDeps, CAMPAIGN, TESTER,
+};
+
+const DEPOSIT_AMOUNT: u64 = 1000000u64;
+
+// (deposit delta OR mine stake) AND not already participated
+fn either_requirement() -> Requirement {
+    Requirement::And(vec![
+        Requirement::Or(vec![
+            Requirement::Condition(Condition::DepositDelta {
+                min: Uint256::from(DEPOSIT_AMOUNT),
+            }),
+            Requirement::Condition(Condition::MineStake {
+                min: Uint256::from(DEPOSIT_AMOUNT),
+                mode: StakeMode::Total,
+            }),
+        ]),
+        Requirement::Condition(Condition::ParticipationCount { max: 1 }),
+    ])
+}
+
+fn setup(deps: &mut MockDeps, stake: u64, deposit_after: u64, participation_count: u64) {
+    setup_requirement(deps, either_requirement());
+
+    mock_gov_stake(deps, stake);
+    mock_participation_count(deps, participation_count);
+    mock_pool_deposit(deps, deposit_after);
+}
+
+#[test]
+fn satisfy_either_condition() {
+    let mut deps = mock_deps();
+    setup(&mut deps, 0, DEPOSIT_AMOUNT, 0);
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+
+    let mut deps = mock_deps();
+    setup(&mut deps, DEPOSIT_AMOUNT, 0, 0);
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+}
+
+#[test]
+fn fail_all_conditions() {
+    let mut deps = mock_deps();
+    setup(&mut deps, 0, 0, 0);
+
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(result.continue_option, QualifiedContinueOption::Ineligible);
+    assert_eq!(
+        result.reason,
+        Some(
+            FailureReason::DepositDeltaTooLow {
+                required: Uint256::from(DEPOSIT_AMOUNT),
+                actual: Uint256::zero(),
+            }
+            .to_json_string()
+            .unwrap()
+        )
+    );
+}
+
+#[test]
+fn fail_already_participated() {
+    let mut deps = mock_deps();
+    setup(&mut deps, DEPOSIT_AMOUNT, DEPOSIT_AMOUNT, 1);
+
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(result.continue_option, QualifiedContinueOption::Ineligible);
+    assert_eq!(
+        result.reason,
+        Some(
+            FailureReason::AlreadyParticipated {
+                participation_count: 1,
+            }
+            .to_json_string()
+            .unwrap()
+        )
+    );
+}
+
+#[test]
+fn skip_deposit_delta_on_query() {
+    let mut deps = mock_deps();
+    setup(&mut deps, 0, 0, 0);
+
+    let result = queries::qualify_without_checking_deposit(
+        deps.as_ref(),
+        qualifier_env(),
+        QualificationMsg {
+            campaign: CAMPAIGN.to_string(),
+            sender: TESTER.to_string(),
+            actor: TESTER.to_string(),
+            referrer: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+}
+
+#[test]
+fn skipped_condition_is_not_negated() {
+    let mut deps = mock_deps();
+    mock_gov_stake(&mut deps, 0);
+    mock_participation_count(&mut deps, 0);
+    mock_pool_deposit(&mut deps, 0);
+
+    instantiate_with_requirement(
+        &mut deps,
+        Requirement::Not(Box::new(Requirement::Condition(Condition::DepositDelta {
+            min: Uint256::from(DEPOSIT_AMOUNT),
+        }))),
+        None,
+    );
+
+    // deposit delta is not checked on the query path, so there is nothing to negate
+    let result = queries::qualify_without_checking_deposit(
+        deps.as_ref(),
+        qualifier_env(),
+        QualificationMsg {
+            campaign: CAMPAIGN.to_string(),
+            sender: TESTER.to_string(),
+            actor: TESTER.to_string(),
+            referrer: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+}
+
+#[test]
+fn missing_referrer_fails_referrer_condition() {
+    let mut deps = mock_deps();
+    mock_gov_stake(&mut deps, 0);
+    mock_participation_count(&mut deps, 0);
+    mock_pool_deposit(&mut deps, 0);
+
+    instantiate_with_requirement(
+        &mut deps,
+        Requirement::Or(vec![
+            Requirement::Condition(Condition::ReferrerMineStake {
+                min: Uint256::from(DEPOSIT_AMOUNT),
+                mode: StakeMode::Total,
+            }),
+            Requirement::Condition(Condition::MineStake {
+                min: Uint256::from(DEPOSIT_AMOUNT),
+                mode: StakeMode::Total,
+            }),
+        ]),
+        None,
+    );
+
+    // leaving out the referrer does not pass the `Or`
+    let result = exec_default(&mut deps, qualifier_env());
+    assert_eq!(result.continue_option, QualifiedContinueOption::Ineligible);
+    assert_eq!(
+        result.reason,
+        Some(
+            FailureReason::MineStakeTooLow {
+                required: Uint256::from(DEPOSIT_AMOUNT),
+                actual: Uint256::zero(),
+            }
+            .to_json_string()
+            .unwrap()
+        )
+    );
+}
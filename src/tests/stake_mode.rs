@@ -0,0 +1,74 @@
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::Uint128;
+use valkyrie_qualifier::QualifiedContinueOption;
+
+use crate::msgs::{FailureReason, GovStakerResponse, VoteOption, VoterInfo};
+use crate::states::{Condition, Requirement, StakeMode};
+use crate::tests::qualify::exec_default;
+use crate::tests::{mock_deps, mock_gov_staker, qualifier_env, setup_requirement, MockDeps};
+
+const STAKE_AMOUNT: u64 = 1000000u64;
+const LOCKED_AMOUNT: u64 = 400000u64;
+
+fn setup(deps: &mut MockDeps, mode: StakeMode, min: u64) {
+    mock_gov_staker(
+        deps,
+        GovStakerResponse {
+            balance: Uint128::from(STAKE_AMOUNT),
+            share: Default::default(),
+            locked_balance: vec![
+                (
+                    1,
+                    VoterInfo {
+                        vote: VoteOption::Yes,
+                        balance: Uint128::from(LOCKED_AMOUNT),
+                    },
+                ),
+                (
+                    2,
+                    VoterInfo {
+                        vote: VoteOption::No,
+                        balance: Uint128::from(LOCKED_AMOUNT / 2),
+                    },
+                ),
+            ],
+        },
+    );
+
+    setup_requirement(
+        deps,
+        Requirement::Condition(Condition::MineStake {
+            min: Uint256::from(min),
+            mode,
+        }),
+    );
+}
+
+#[test]
+fn stake_amount_by_mode() {
+    for (mode, amount) in [
+        (StakeMode::Total, STAKE_AMOUNT),
+        (StakeMode::Unlocked, STAKE_AMOUNT - LOCKED_AMOUNT),
+        (StakeMode::Locked, LOCKED_AMOUNT),
+    ] {
+        let mut deps = mock_deps();
+        setup(&mut deps, mode.clone(), amount);
+        let result = exec_default(&mut deps, qualifier_env());
+        assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+
+        let mut deps = mock_deps();
+        setup(&mut deps, mode, amount + 1);
+        let result = exec_default(&mut deps, qualifier_env());
+        assert_eq!(
+            result.reason,
+            Some(
+                FailureReason::MineStakeTooLow {
+                    required: Uint256::from(amount + 1),
+                    actual: Uint256::from(amount),
+                }
+                .to_json_string()
+                .unwrap()
+            )
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::testing::mock_info;
+use valkyrie_qualifier::QualifiedContinueOption;
+
+use crate::msgs::StatsResponse;
+use crate::queries;
+use crate::states::Stats;
+use crate::tests::qualify::{exec, exec_default, instantiate_with, restrict_to_campaign};
+use crate::tests::{
+    mock_deps, mock_gov_stake, mock_participation_count, mock_pool_deposit,
+    qualifier_creator_sender, qualifier_env, MockDeps, CAMPAIGN, TESTER,
+};
+
+const DEPOSIT_AMOUNT: u64 = 1000000u64;
+const CAMPAIGN2: &str = "terra1jwf5w8rnwvhvdhvwyyqn2uwwfx0wx9s7pcyqlj";
+
+fn setup(deps: &mut MockDeps) {
+    mock_gov_stake(deps, 0);
+    mock_participation_count(deps, 0);
+    mock_pool_deposit(deps, 0);
+
+    instantiate_with(deps, DEPOSIT_AMOUNT, 0, None);
+}
+
+fn campaign_stats(
+    deps: &MockDeps,
+    start_after: Option<&str>,
+    limit: Option<u32>,
+) -> Vec<(String, Stats)> {
+    queries::campaign_stats(
+        deps.as_ref(),
+        qualifier_env(),
+        start_after.map(|c| c.to_string()),
+        limit,
+    )
+    .unwrap()
+    .campaigns
+}
+
+#[test]
+fn aggregate_stats() {
+    let mut deps = mock_deps();
+    setup(&mut deps);
+    restrict_to_campaign(&mut deps);
+
+    let env = qualifier_env();
+    let response = queries::stats(deps.as_ref(), env.clone()).unwrap();
+    assert_eq!(
+        response,
+        StatsResponse {
+            global: Stats::default(),
+            failure_reasons: vec![],
+        }
+    );
+    assert_eq!(campaign_stats(&deps, None, None), vec![]);
+
+    super::prepare::exec(&mut deps, env.clone(), mock_info(TESTER, &[])).unwrap();
+    mock_pool_deposit(&mut deps, DEPOSIT_AMOUNT * 2);
+
+    let result = exec_default(&mut deps, env.clone());
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+
+    // the snapshot was consumed
+    let result = exec_default(&mut deps, env.clone());
+    assert_eq!(result.continue_option, QualifiedContinueOption::Ineligible);
+
+    let stats = Stats {
+        total_count: 2,
+        eligible_count: 1,
+        failed_count: 1,
+        total_deposit_delta: Uint256::from(DEPOSIT_AMOUNT * 2),
+    };
+    let response = queries::stats(deps.as_ref(), env).unwrap();
+    assert_eq!(
+        response,
+        StatsResponse {
+            global: stats.clone(),
+            failure_reasons: vec![("prepare_status_not_found".to_string(), 1)],
+        }
+    );
+    assert_eq!(
+        campaign_stats(&deps, None, None),
+        vec![(CAMPAIGN.to_string(), stats)]
+    );
+}
+
+#[test]
+fn paginate_campaign_stats() {
+    let mut deps = mock_deps();
+    setup(&mut deps);
+    restrict_to_campaign(&mut deps);
+    super::campaign::exec_add(
+        &mut deps,
+        qualifier_env(),
+        qualifier_creator_sender(),
+        CAMPAIGN2,
+    )
+    .unwrap();
+
+    let env = qualifier_env();
+    for campaign in [CAMPAIGN, CAMPAIGN2] {
+        exec(
+            &mut deps,
+            env.clone(),
+            mock_info(campaign, &[]),
+            campaign.to_string(),
+            TESTER.to_string(),
+            TESTER.to_string(),
+            None,
+        )
+        .unwrap();
+    }
+
+    let mut campaigns = [CAMPAIGN.to_string(), CAMPAIGN2.to_string()];
+    campaigns.sort();
+
+    let response = campaign_stats(&deps, None, Some(1));
+    assert_eq!(response.len(), 1);
+    assert_eq!(response[0].0, campaigns[0]);
+
+    let response = campaign_stats(&deps, Some(&campaigns[0]), None);
+    assert_eq!(response.len(), 1);
+    assert_eq!(response[0].0, campaigns[1]);
+}
+
+#[test]
+fn count_registered_campaign_only() {
+    let mut deps = mock_deps();
+    setup(&mut deps);
+
+    // forged by a third party
+    exec(
+        &mut deps,
+        qualifier_env(),
+        mock_info(TESTER, &[]),
+        CAMPAIGN.to_string(),
+        TESTER.to_string(),
+        TESTER.to_string(),
+        None,
+    )
+    .unwrap();
+    // not registered
+    exec_default(&mut deps, qualifier_env());
+
+    let response = queries::stats(deps.as_ref(), qualifier_env()).unwrap();
+    assert_eq!(response.global, Stats::default());
+    assert_eq!(campaign_stats(&deps, None, None), vec![]);
+}
@@ -0,0 +1,99 @@
+use cosmwasm_bignumber::Uint256;
+use cosmwasm_std::testing::mock_info;
+use cosmwasm_std::{Env, MessageInfo, Response};
+
+use crate::errors::ContractError;
+use crate::executions::{update_requirement, ExecuteResult};
+use crate::msgs::to_json_string;
+use crate::states::{Condition, Requirement, StakeMode};
+use crate::tests::{default_requirement, mock_deps, MockDeps, TESTER};
+
+pub fn exec(
+    deps: &mut MockDeps,
+    env: Env,
+    info: MessageInfo,
+    requirement: Requirement,
+) -> ExecuteResult {
+    update_requirement(deps.as_mut(), env, info, requirement)
+}
+
+#[test]
+fn succeed() {
+    let mut deps = mock_deps();
+
+    let (env, info, _) = super::instantiate::default(&mut deps);
+
+    let requirement = Requirement::Or(vec![
+        Requirement::Condition(Condition::DepositDelta {
+            min: Uint256::from(500u64),
+        }),
+        Requirement::Condition(Condition::MineStake {
+            min: Uint256::from(1000u64),
+            mode: StakeMode::Total,
+        }),
+    ]);
+
+    let response = exec(&mut deps, env, info, requirement.clone()).unwrap();
+    assert_eq!(
+        response,
+        Response::default()
+            .add_attribute("action", "update_requirement")
+            .add_attribute(
+                "prev_requirement",
+                to_json_string(&default_requirement(0, 0)).unwrap()
+            )
+            .add_attribute("requirement", to_json_string(&requirement).unwrap())
+    );
+
+    assert_eq!(
+        Requirement::load(deps.as_ref().storage).unwrap(),
+        requirement
+    );
+}
+
+#[test]
+fn failed_without_permission() {
+    let mut deps = mock_deps();
+
+    let (env, _, _) = super::instantiate::default(&mut deps);
+
+    let result = exec(
+        &mut deps,
+        env,
+        mock_info(TESTER, &[]),
+        default_requirement(500, 0),
+    );
+    assert_eq!(result, Err(ContractError::Unauthorized {}));
+}
+
+#[test]
+fn failed_with_invalid_requirement() {
+    let mut deps = mock_deps();
+
+    let (env, info, _) = super::instantiate::default(&mut deps);
+
+    let result = exec(
+        &mut deps,
+        env.clone(),
+        info.clone(),
+        Requirement::And(vec![]),
+    );
+    assert_eq!(
+        result,
+        Err(ContractError::InvalidArgument {
+            msg: "Empty and requirement".to_string()
+        })
+    );
+
+    let mut requirement = Requirement::Condition(Condition::ParticipationCount { max: 1 });
+    for _ in 0..10 {
+        requirement = Requirement::Not(Box::new(requirement));
+    }
+    let result = exec(&mut deps, env, info, requirement);
+    assert_eq!(
+        result,
+        Err(ContractError::InvalidArgument {
+            msg: "Requirement is too complex".to_string()
+        })
+    );
+}
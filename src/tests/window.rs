@@ -0,0 +1,129 @@
+use cosmwasm_std::Env;
+use cw0::Expiration;
+use valkyrie_qualifier::QualifiedContinueOption;
+
+use crate::errors::ContractError;
+use crate::executions::ExecuteResult;
+use crate::msgs::{ConfigureMsg, ContinueOptionOverride, FailureReason};
+use crate::states::{Condition, Requirement};
+use crate::tests::qualify::{exec_default, instantiate_with_requirement};
+use crate::tests::{
+    exec_configure, mock_deps, mock_participation_count, mock_pool_deposit, qualifier_env, MockDeps,
+};
+
+fn configure_window(
+    deps: &mut MockDeps,
+    start_time: Option<Expiration>,
+    end_time: Option<Expiration>,
+    continue_option_on_window_fail: Option<ContinueOptionOverride>,
+) -> ExecuteResult {
+    exec_configure(
+        deps,
+        ConfigureMsg {
+            start_time,
+            end_time,
+            continue_option_on_window_fail,
+            ..Default::default()
+        },
+    )
+}
+
+fn setup(deps: &mut MockDeps, start_height: u64, end_height: u64) {
+    mock_participation_count(deps, 0);
+    mock_pool_deposit(deps, 0);
+
+    instantiate_with_requirement(
+        deps,
+        Requirement::Condition(Condition::ParticipationCount { max: 1 }),
+        None,
+    );
+    configure_window(
+        deps,
+        Some(Expiration::AtHeight(start_height)),
+        Some(Expiration::AtHeight(end_height)),
+        None,
+    )
+    .unwrap();
+}
+
+fn env_at(height: u64) -> Env {
+    let mut env = qualifier_env();
+    env.block.height = height;
+
+    env
+}
+
+#[test]
+fn qualify_within_window() {
+    let mut deps = mock_deps();
+    setup(&mut deps, 100, 200);
+
+    let result = exec_default(&mut deps, env_at(99));
+    assert_eq!(result.continue_option, QualifiedContinueOption::Ineligible);
+    assert_eq!(
+        result.reason,
+        Some(
+            FailureReason::NotStarted {
+                start_time: Expiration::AtHeight(100),
+            }
+            .to_json_string()
+            .unwrap()
+        )
+    );
+
+    let result = exec_default(&mut deps, env_at(100));
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+
+    let result = exec_default(&mut deps, env_at(200));
+    assert_eq!(
+        result.reason,
+        Some(
+            FailureReason::Ended {
+                end_time: Expiration::AtHeight(200),
+            }
+            .to_json_string()
+            .unwrap()
+        )
+    );
+
+    // the bound is removed with `Never`
+    configure_window(&mut deps, None, Some(Expiration::Never {}), None).unwrap();
+    let result = exec_default(&mut deps, env_at(200));
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+}
+
+#[test]
+fn continue_option_on_window_fail() {
+    let mut deps = mock_deps();
+    setup(&mut deps, 100, 200);
+    configure_window(
+        &mut deps,
+        None,
+        None,
+        Some(ContinueOptionOverride::Eligible),
+    )
+    .unwrap();
+
+    let result = exec_default(&mut deps, env_at(200));
+    assert_eq!(result.continue_option, QualifiedContinueOption::Eligible);
+
+    // regular failures are not affected
+    mock_participation_count(&mut deps, 1);
+    let result = exec_default(&mut deps, env_at(150));
+    assert_eq!(result.continue_option, QualifiedContinueOption::Ineligible);
+
+    // falls back to `continue_option_on_fail`
+    configure_window(&mut deps, None, None, Some(ContinueOptionOverride::Unset)).unwrap();
+    mock_participation_count(&mut deps, 0);
+    let result = exec_default(&mut deps, env_at(200));
+    assert_eq!(result.continue_option, QualifiedContinueOption::Ineligible);
+}
+
+#[test]
+fn invalid_window() {
+    let mut deps = mock_deps();
+    setup(&mut deps, 100, 200);
+
+    let result = configure_window(&mut deps, Some(Expiration::AtHeight(200)), None, None);
+    assert!(matches!(result, Err(ContractError::InvalidArgument { .. })));
+}